@@ -0,0 +1,119 @@
+use crate::game::{Color, CubeSet, Game};
+
+pub fn minimal_bag(games: &[Game]) -> CubeSet {
+    games
+        .iter()
+        .map(Game::max_per_color)
+        .fold(CubeSet::default(), CubeSet::max)
+}
+
+pub fn largest_consistent_subset(games: &[Game], bag_size: i32) -> BagFit {
+    let minimums: Vec<CubeSet> = games.iter().map(Game::max_per_color).collect();
+    let candidates = |color: Color| {
+        let mut counts: Vec<i32> = minimums.iter().map(|cubes| cubes.get(color)).collect();
+        counts.push(0);
+        counts.sort_unstable();
+        counts.dedup();
+        counts
+    };
+    let (red_candidates, green_candidates) = (candidates(Color::Red), candidates(Color::Green));
+
+    // The bag only ever needs as many red and green cubes as some game requires, so trying
+    // those counts and giving every remaining cube to blue covers every useful split.
+    let mut best: Option<(usize, CubeSet)> = None;
+    for &red in red_candidates.iter().take_while(|&&red| red <= bag_size) {
+        for &green in green_candidates
+            .iter()
+            .take_while(|&&green| red + green <= bag_size)
+        {
+            let bag = CubeSet::new(red, green, bag_size - red - green);
+            let count = minimums.iter().filter(|cubes| cubes.fits_in(bag)).count();
+            if !matches!(best, Some((best_count, _)) if best_count >= count) {
+                best = Some((count, bag));
+            }
+        }
+    }
+
+    let bag = best.map_or(CubeSet::default(), |(_, bag)| bag);
+    let game_ids = games
+        .iter()
+        .zip(&minimums)
+        .filter(|(_, cubes)| cubes.fits_in(bag))
+        .map(|(game, _)| game.id)
+        .collect();
+    BagFit { bag, game_ids }
+}
+
+pub fn impossibility_thresholds(games: &[Game], color: Color) -> Vec<Threshold> {
+    let mut thresholds: Vec<Threshold> = games
+        .iter()
+        .map(|game| Threshold {
+            game_id: game.id,
+            min_cubes: game.max_per_color().get(color),
+        })
+        .collect();
+    thresholds.sort_by_key(|threshold| (threshold.min_cubes, threshold.game_id));
+    thresholds
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BagFit {
+    pub bag: CubeSet,
+    pub game_ids: Vec<i32>,
+}
+
+// A game becomes impossible once the bag holds fewer than `min_cubes` cubes of the color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Threshold {
+    pub game_id: i32,
+    pub min_cubes: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::games_parser;
+
+    fn example_games() -> Vec<Game> {
+        games_parser(include_str!("../example.txt")).unwrap().1
+    }
+
+    #[test]
+    fn minimal_bag_example() {
+        let games = example_games();
+        let bag = minimal_bag(&games);
+        assert_eq!(bag, CubeSet::new(20, 13, 15));
+        assert!(games.iter().all(|game| game.is_possible(bag)));
+    }
+
+    #[test]
+    fn largest_consistent_subset_example() {
+        let games = example_games();
+
+        let fit = largest_consistent_subset(&games, 48);
+        assert_eq!(fit.game_ids, vec![1, 2, 3, 4, 5]);
+        assert_eq!(fit.bag.total(), 48);
+
+        let fit = largest_consistent_subset(&games, 15);
+        assert_eq!(fit.game_ids, vec![1, 2, 5]);
+        assert_eq!(fit.bag, CubeSet::new(6, 3, 6));
+
+        let fit = largest_consistent_subset(&games, 12);
+        assert_eq!(fit.game_ids.len(), 1);
+        assert_eq!(fit.bag.total(), 12);
+
+        let fit = largest_consistent_subset(&games, 0);
+        assert!(fit.game_ids.is_empty());
+    }
+
+    #[test]
+    fn impossibility_thresholds_example() {
+        let games = example_games();
+        let thresholds = impossibility_thresholds(&games, Color::Red);
+        let ordered: Vec<_> = thresholds
+            .iter()
+            .map(|threshold| (threshold.game_id, threshold.min_cubes))
+            .collect();
+        assert_eq!(ordered, vec![(2, 1), (1, 4), (5, 6), (4, 14), (3, 20)]);
+    }
+}
@@ -0,0 +1,233 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, line_ending, multispace0, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, terminated},
+    IResult, Parser,
+};
+
+// Fails unless every line is a game, so a malformed line cannot silently drop the rest.
+pub fn games_parser(input: &str) -> IResult<&str, Vec<Game>> {
+    all_consuming(terminated(
+        separated_list1(line_ending, game_parser),
+        multispace0,
+    ))(input)
+}
+
+fn game_parser(input: &str) -> IResult<&str, Game> {
+    let (input, id) = preceded(tag("Game "), complete::i32)(input)?;
+    let (input, _) = tag(": ")(input)?;
+    let (input, rounds) = separated_list1(tag("; "), round_parser)(input)?;
+    Ok((input, Game { id, rounds }))
+}
+
+fn round_parser(input: &str) -> IResult<&str, Round> {
+    separated_list1(tag(", "), draw_parser)
+        .map(|draws| Round { draws })
+        .parse(input)
+}
+
+fn draw_parser(input: &str) -> IResult<&str, Draw> {
+    separated_pair(complete::i32, space1, color_parser)
+        .map(|(count, color)| Draw { count, color })
+        .parse(input)
+}
+
+fn color_parser(input: &str) -> IResult<&str, Color> {
+    alt((
+        tag("red").map(|_| Color::Red),
+        tag("green").map(|_| Color::Green),
+        tag("blue").map(|_| Color::Blue),
+    ))(input)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: i32,
+    pub rounds: Vec<Round>,
+}
+
+impl Game {
+    pub fn max_per_color(&self) -> CubeSet {
+        self.rounds
+            .iter()
+            .map(Round::cubes)
+            .fold(CubeSet::default(), |max, cubes| max.max(cubes))
+    }
+
+    pub fn total_cubes(&self) -> i32 {
+        self.rounds.iter().map(|round| round.cubes().total()).sum()
+    }
+
+    pub fn violating_round(&self, bag: CubeSet) -> Option<(usize, &Round)> {
+        self.rounds
+            .iter()
+            .enumerate()
+            .find(|(_, round)| !round.cubes().fits_in(bag))
+    }
+
+    pub fn is_possible(&self, bag: CubeSet) -> bool {
+        self.violating_round(bag).is_none()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub draws: Vec<Draw>,
+}
+
+impl Round {
+    pub fn cubes(&self) -> CubeSet {
+        self.draws
+            .iter()
+            .fold(CubeSet::default(), |mut cubes, draw| {
+                *cubes.get_mut(draw.color) += draw.count;
+                cubes
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Draw {
+    pub count: i32,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+impl Color {
+    pub const ALL: [Color; 3] = [Color::Red, Color::Green, Color::Blue];
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CubeSet {
+    pub red: i32,
+    pub green: i32,
+    pub blue: i32,
+}
+
+impl CubeSet {
+    pub const fn new(red: i32, green: i32, blue: i32) -> Self {
+        Self { red, green, blue }
+    }
+
+    pub fn get(&self, color: Color) -> i32 {
+        match color {
+            Color::Red => self.red,
+            Color::Green => self.green,
+            Color::Blue => self.blue,
+        }
+    }
+
+    pub fn get_mut(&mut self, color: Color) -> &mut i32 {
+        match color {
+            Color::Red => &mut self.red,
+            Color::Green => &mut self.green,
+            Color::Blue => &mut self.blue,
+        }
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            red: self.red.max(other.red),
+            green: self.green.max(other.green),
+            blue: self.blue.max(other.blue),
+        }
+    }
+
+    pub fn fits_in(&self, bag: CubeSet) -> bool {
+        Color::ALL
+            .into_iter()
+            .all(|color| self.get(color) <= bag.get(color))
+    }
+
+    pub fn total(&self) -> i32 {
+        self.red + self.green + self.blue
+    }
+
+    pub fn power(&self) -> i32 {
+        self.red * self.green * self.blue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_games() -> Vec<Game> {
+        let (input, games) = games_parser(include_str!("../example.txt")).unwrap();
+        assert_eq!(input, "");
+        games
+    }
+
+    #[test]
+    fn parses_every_draw() {
+        let games = example_games();
+        assert_eq!(games.len(), 5);
+        assert_eq!(games[0].id, 1);
+        assert_eq!(
+            games[0].rounds[1],
+            Round {
+                draws: vec![
+                    Draw {
+                        count: 1,
+                        color: Color::Red
+                    },
+                    Draw {
+                        count: 2,
+                        color: Color::Green
+                    },
+                    Draw {
+                        count: 6,
+                        color: Color::Blue
+                    },
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parses_crlf_and_trailing_newline() {
+        let (input, games) = games_parser("Game 1: 1 red\r\nGame 2: 2 blue, 3 green\r\n").unwrap();
+        assert_eq!(input, "");
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[1].rounds[0].cubes(), CubeSet::new(0, 3, 2));
+    }
+
+    #[test]
+    fn rejects_unparsable_lines() {
+        assert!(games_parser("Game 1: 3 blue\nGame 2: 3 purple\nGame 3: 4 red\n").is_err());
+        assert!(games_parser("Game 1: 3 blue\nnot a game").is_err());
+    }
+
+    #[test]
+    fn max_per_color_example() {
+        let games = example_games();
+        assert_eq!(games[0].max_per_color(), CubeSet::new(4, 2, 6));
+        assert_eq!(games[2].max_per_color(), CubeSet::new(20, 13, 6));
+    }
+
+    #[test]
+    fn total_cubes_example() {
+        let games = example_games();
+        assert_eq!(games[0].total_cubes(), 18);
+        assert_eq!(games[4].total_cubes(), 15);
+    }
+
+    #[test]
+    fn violating_round_example() {
+        let games = example_games();
+        let bag = CubeSet::new(12, 13, 14);
+        assert_eq!(games[0].violating_round(bag), None);
+        let (index, round) = games[2].violating_round(bag).unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(round.cubes(), CubeSet::new(20, 8, 6));
+    }
+}
@@ -1,3 +1,5 @@
+pub mod bag;
+pub mod game;
 mod part1;
 mod part2;
 
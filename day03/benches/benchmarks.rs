@@ -19,3 +19,9 @@ fn part2() {
     let input = include_str!("../input.txt");
     day03::part2(divan::black_box(input));
 }
+
+#[divan::bench]
+fn streaming() {
+    let input = include_str!("../input.txt");
+    day03::streaming::solve(divan::black_box(input.as_bytes())).unwrap();
+}
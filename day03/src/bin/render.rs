@@ -0,0 +1,21 @@
+use day03::{
+    gear::GearRule,
+    render::{render, Format},
+};
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+fn main() {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    let format = std::env::args()
+        .nth(1)
+        .map_or(Ok(Format::Ansi), |format| Format::try_from(format.as_str()))
+        .unwrap();
+
+    let input = include_str!("../../input.txt");
+    print!("{}", render(input, &GearRule::default(), format));
+}
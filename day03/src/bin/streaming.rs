@@ -0,0 +1,25 @@
+use std::{fs::File, io::BufReader};
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+fn main() {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    let totals = match std::env::args().nth(1) {
+        Some(path) => {
+            let file = File::open(path).expect("schematic file should be readable");
+            day03::streaming::solve(BufReader::new(file))
+        }
+        None => {
+            let input = include_str!("../../input.txt");
+            day03::streaming::solve(input.as_bytes())
+        }
+    }
+    .expect("schematic should be streamable");
+
+    println!("{}", totals.part_number_sum);
+    println!("{}", totals.gear_ratio_sum);
+}
@@ -0,0 +1,128 @@
+use crate::schematic::{Schematic, SchematicNumber};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GearRule {
+    symbols: Vec<char>,
+    count: AdjacentCount,
+    combine: Combine,
+}
+
+impl GearRule {
+    pub fn new(
+        symbols: impl IntoIterator<Item = char>,
+        count: AdjacentCount,
+        combine: Combine,
+    ) -> Self {
+        Self {
+            symbols: symbols.into_iter().collect(),
+            count,
+            combine,
+        }
+    }
+
+    fn is_gear(&self, symbol: char, adjacent_count: usize) -> bool {
+        self.symbols.contains(&symbol) && self.count.matches(adjacent_count)
+    }
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        Self::new(['*'], AdjacentCount::Exactly(2), Combine::Product)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdjacentCount {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl AdjacentCount {
+    fn matches(&self, adjacent_count: usize) -> bool {
+        match *self {
+            AdjacentCount::Exactly(n) => adjacent_count == n,
+            AdjacentCount::AtLeast(n) => adjacent_count >= n,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Combine {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Combine {
+    fn apply<'a>(&self, nums: impl Iterator<Item = &'a SchematicNumber>) -> i64 {
+        let values = nums.map(|num| num.value);
+        match self {
+            Combine::Product => values.product(),
+            Combine::Sum => values.sum(),
+            Combine::Max => values.max().unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Gear {
+    pub symbol_index: usize,
+    pub ratio: i64,
+}
+
+impl Schematic {
+    pub fn gears<'a>(&'a self, rule: &'a GearRule) -> impl Iterator<Item = Gear> + 'a {
+        self.symbols()
+            .iter()
+            .enumerate()
+            .filter(|&(symbol_index, symbol)| {
+                rule.is_gear(
+                    symbol.value,
+                    self.adjacent_number_indices(symbol_index).len(),
+                )
+            })
+            .map(|(symbol_index, _)| Gear {
+                symbol_index,
+                ratio: rule.combine.apply(self.adjacent_numbers(symbol_index)),
+            })
+    }
+
+    pub fn gear_total(&self, rule: &GearRule) -> i64 {
+        self.gears(rule).map(|gear| gear.ratio).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_rule_matches_part2() {
+        let schematic = Schematic::new(include_str!("../example.txt"));
+        assert_eq!(schematic.gear_total(&GearRule::default()), 467835);
+    }
+
+    #[test]
+    fn variant_rules_example() {
+        let schematic = Schematic::new(include_str!("../example.txt"));
+
+        let rule = GearRule::new(['*'], AdjacentCount::Exactly(2), Combine::Sum);
+        assert_eq!(schematic.gear_total(&rule), 467 + 35 + 755 + 598);
+
+        let rule = GearRule::new(['*'], AdjacentCount::AtLeast(1), Combine::Max);
+        assert_eq!(schematic.gear_total(&rule), 467 + 617 + 755);
+
+        let rule = GearRule::new(['#', '+', '$'], AdjacentCount::Exactly(1), Combine::Product);
+        assert_eq!(schematic.gear_total(&rule), 633 + 592 + 664);
+    }
+
+    #[test]
+    fn lists_matching_gears() {
+        let schematic = Schematic::new(include_str!("../example.txt"));
+        let rule = GearRule::new(['*'], AdjacentCount::Exactly(1), Combine::Product);
+        let gears: Vec<Gear> = schematic.gears(&rule).collect();
+        assert_eq!(gears.len(), 1);
+        assert_eq!(schematic.symbols()[gears[0].symbol_index].value, '*');
+        assert_eq!(gears[0].ratio, 617);
+    }
+}
@@ -1,6 +1,183 @@
+pub mod gear;
 mod part1;
 pub mod part1_set;
 mod part2;
+pub mod render;
+pub mod schematic;
+pub mod streaming;
 
 pub use part1::part1;
 pub use part2::part2;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(input: &str) -> (i64, i64) {
+        let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let cell = |x: i64, y: i64| {
+            if x < 0 || y < 0 {
+                return '.';
+            }
+            grid.get(y as usize)
+                .and_then(|row| row.get(x as usize))
+                .copied()
+                .unwrap_or('.')
+        };
+        let is_symbol = |c: char| !c.is_ascii_digit() && c != '.';
+
+        let mut numbers = vec![];
+        for (y, row) in grid.iter().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                if !row[x].is_ascii_digit() {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                let mut value = 0;
+                while x < row.len() && row[x].is_ascii_digit() {
+                    value = value * 10 + row[x].to_digit(10).unwrap() as i64;
+                    x += 1;
+                }
+                numbers.push((value, y as i64, start as i64, x as i64 - 1));
+            }
+        }
+
+        let part_number_sum = numbers
+            .iter()
+            .filter(|&&(_, y, start, end)| {
+                (y - 1..=y + 1).any(|ny| (start - 1..=end + 1).any(|nx| is_symbol(cell(nx, ny))))
+            })
+            .map(|&(value, ..)| value)
+            .sum();
+
+        let mut gear_ratio_sum = 0;
+        for (y, row) in grid.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                if c != '*' {
+                    continue;
+                }
+                let (x, y) = (x as i64, y as i64);
+                let adjacent: Vec<i64> = numbers
+                    .iter()
+                    .filter(|&&(_, ny, start, end)| {
+                        (ny - y).abs() <= 1 && start <= x + 1 && x - 1 <= end
+                    })
+                    .map(|&(value, ..)| value)
+                    .collect();
+                if adjacent.len() == 2 {
+                    gear_ratio_sum += adjacent[0] * adjacent[1];
+                }
+            }
+        }
+
+        (part_number_sum, gear_ratio_sum)
+    }
+
+    fn assert_solvers_agree(input: &str) {
+        let (part_number_sum, gear_ratio_sum) = reference(input);
+        assert_eq!(part1(input), part_number_sum, "part1 {input:?}");
+        assert_eq!(
+            part1_set::part1(input),
+            part_number_sum,
+            "part1_set {input:?}"
+        );
+        assert_eq!(part2(input), gear_ratio_sum, "part2 {input:?}");
+
+        let totals = streaming::solve(input.as_bytes()).unwrap();
+        assert_eq!(
+            totals,
+            streaming::Totals {
+                part_number_sum,
+                gear_ratio_sum
+            },
+            "streaming {input:?}"
+        );
+    }
+
+    #[test]
+    fn number_on_last_column() {
+        assert_solvers_agree("..12\n...*\n..3.");
+        assert_eq!(part2("..12\n...*\n..3."), 36);
+    }
+
+    #[test]
+    fn numbers_in_corners() {
+        let input = "1..2\n.**.\n3..4";
+        assert_eq!(part1(input), 10);
+        assert_eq!(part2(input), 3 + 8);
+        assert_solvers_agree(input);
+    }
+
+    #[test]
+    fn number_spanning_whole_row() {
+        let input = "1234\n#...";
+        assert_eq!(part1(input), 1234);
+        assert_solvers_agree(input);
+    }
+
+    #[test]
+    fn symbol_past_end_of_shorter_row() {
+        let input = "12\n..*\n4";
+        assert_eq!(part1(input), 12);
+        assert_eq!(part2(input), 0);
+        assert_solvers_agree(input);
+
+        let input = "12\n..*\n..5";
+        assert_eq!(part2(input), 60);
+        assert_solvers_agree(input);
+    }
+
+    #[test]
+    fn number_past_end_of_shorter_row() {
+        let input = "*\n.\n..7\n#";
+        assert_eq!(part1(input), 0);
+        assert_solvers_agree(input);
+
+        let input = "...7\n*\n..#";
+        assert_eq!(part1(input), 0);
+        assert_solvers_agree(input);
+    }
+
+    #[test]
+    fn numbers_beyond_i32() {
+        assert_solvers_agree("12345678901*");
+        assert_eq!(part1("12345678901*"), 12_345_678_901);
+        assert_solvers_agree("99999*\n99999.");
+        assert_eq!(part2("99999*\n99999."), 99_999 * 99_999);
+    }
+
+    #[test]
+    fn empty_rows() {
+        assert_solvers_agree("");
+        assert_solvers_agree("\n\n");
+        assert_solvers_agree("5\n\n*5");
+    }
+
+    #[test]
+    fn exhaustive_small_ragged_grids() {
+        let alphabet = ['.', '5', '*'];
+        let rows: Vec<String> = (0..=3)
+            .flat_map(|len| {
+                (0..alphabet.len().pow(len)).map(move |mut n| {
+                    (0..len)
+                        .map(|_| {
+                            let c = alphabet[n % alphabet.len()];
+                            n /= alphabet.len();
+                            c
+                        })
+                        .collect()
+                })
+            })
+            .collect();
+
+        for top in rows.iter() {
+            for middle in rows.iter() {
+                for bottom in rows.iter() {
+                    assert_solvers_agree(&format!("{top}\n{middle}\n{bottom}"));
+                }
+            }
+        }
+    }
+}
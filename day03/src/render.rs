@@ -0,0 +1,293 @@
+use crate::{
+    gear::GearRule,
+    schematic::{Position, Schematic},
+};
+use std::{collections::HashSet, fmt::Write};
+
+pub fn render(input: &str, rule: &GearRule, format: Format) -> String {
+    let schematic = Schematic::new(input);
+    let gear_positions: HashSet<Position> = schematic
+        .gears(rule)
+        .map(|gear| schematic.symbols()[gear.symbol_index].position)
+        .collect();
+    let symbol_positions: HashSet<Position> = schematic
+        .symbols()
+        .iter()
+        .map(|symbol| symbol.position)
+        .collect();
+
+    let rows: Vec<Vec<(Cell, char)>> = input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    let pos = Position::new(x, y);
+                    let cell = if let Some(number_index) = schematic.number_index_at(pos) {
+                        if schematic.is_part_number(number_index) {
+                            Cell::PartNumber
+                        } else {
+                            Cell::NonPartNumber
+                        }
+                    } else if gear_positions.contains(&pos) {
+                        Cell::Gear
+                    } else if symbol_positions.contains(&pos) {
+                        Cell::Symbol
+                    } else {
+                        Cell::Empty
+                    };
+                    (cell, c)
+                })
+                .collect()
+        })
+        .collect();
+
+    let legend = Legend::new(&schematic, rule);
+    match format {
+        Format::Ansi => render_ansi(&rows, &legend),
+        Format::Html => render_html(&rows, &legend),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ansi,
+    Html,
+}
+
+impl TryFrom<&str> for Format {
+    type Error = String;
+
+    fn try_from(format: &str) -> Result<Self, Self::Error> {
+        Ok(match format {
+            "ansi" => Format::Ansi,
+            "html" => Format::Html,
+            _ => return Err(format!("\"{format}\" is not a valid render format.")),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cell {
+    PartNumber,
+    NonPartNumber,
+    Symbol,
+    Gear,
+    Empty,
+}
+
+impl Cell {
+    const ALL: [Cell; 5] = [
+        Cell::PartNumber,
+        Cell::NonPartNumber,
+        Cell::Symbol,
+        Cell::Gear,
+        Cell::Empty,
+    ];
+
+    fn ansi_code(&self) -> &'static str {
+        match self {
+            Cell::PartNumber => "32",
+            Cell::NonPartNumber => "31",
+            Cell::Symbol => "33",
+            Cell::Gear => "1;35",
+            Cell::Empty => "2",
+        }
+    }
+
+    fn class_name(&self) -> &'static str {
+        match self {
+            Cell::PartNumber => "part",
+            Cell::NonPartNumber => "non-part",
+            Cell::Symbol => "symbol",
+            Cell::Gear => "gear",
+            Cell::Empty => "empty",
+        }
+    }
+
+    fn html_color(&self) -> &'static str {
+        match self {
+            Cell::PartNumber => "#2e7d32",
+            Cell::NonPartNumber => "#c62828",
+            Cell::Symbol => "#f9a825",
+            Cell::Gear => "#ad1457; font-weight: bold",
+            Cell::Empty => "#9e9e9e",
+        }
+    }
+}
+
+struct Legend {
+    part_numbers: (usize, i64),
+    non_part_numbers: (usize, i64),
+    symbols: usize,
+    gears: (usize, i64),
+}
+
+impl Legend {
+    fn new(schematic: &Schematic, rule: &GearRule) -> Self {
+        let (part_numbers, non_part_numbers) = schematic.numbers().iter().enumerate().fold(
+            ((0, 0), (0, 0)),
+            |(mut part, mut non_part), (number_index, num)| {
+                let totals = if schematic.is_part_number(number_index) {
+                    &mut part
+                } else {
+                    &mut non_part
+                };
+                totals.0 += 1;
+                totals.1 += num.value;
+                (part, non_part)
+            },
+        );
+        let gears = schematic.gears(rule).fold((0, 0), |(count, total), gear| {
+            (count + 1, total + gear.ratio)
+        });
+
+        Self {
+            part_numbers,
+            non_part_numbers,
+            symbols: schematic.symbols().len(),
+            gears,
+        }
+    }
+
+    fn lines(&self) -> [(Cell, String); 4] {
+        [
+            (
+                Cell::PartNumber,
+                format!(
+                    "part numbers: {} (sum {})",
+                    self.part_numbers.0, self.part_numbers.1
+                ),
+            ),
+            (
+                Cell::NonPartNumber,
+                format!(
+                    "non-part numbers: {} (sum {})",
+                    self.non_part_numbers.0, self.non_part_numbers.1
+                ),
+            ),
+            (Cell::Symbol, format!("symbols: {}", self.symbols)),
+            (
+                Cell::Gear,
+                format!("gears: {} (ratio sum {})", self.gears.0, self.gears.1),
+            ),
+        ]
+    }
+}
+
+fn runs(row: &[(Cell, char)]) -> impl Iterator<Item = (Cell, String)> + '_ {
+    row.chunk_by(|a, b| a.0 == b.0)
+        .map(|run| (run[0].0, run.iter().map(|&(_, c)| c).collect()))
+}
+
+fn render_ansi(rows: &[Vec<(Cell, char)>], legend: &Legend) -> String {
+    let mut output = String::new();
+    for row in rows {
+        for (cell, text) in runs(row) {
+            write!(output, "\x1b[{}m{text}\x1b[0m", cell.ansi_code()).unwrap();
+        }
+        output.push('\n');
+    }
+
+    output.push('\n');
+    for (cell, line) in legend.lines() {
+        writeln!(output, "\x1b[{}m■\x1b[0m {line}", cell.ansi_code()).unwrap();
+    }
+    output
+}
+
+fn render_html(rows: &[Vec<(Cell, char)>], legend: &Legend) -> String {
+    let mut output = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Schematic</title>\n<style>\n",
+    );
+    output.push_str("body { font-family: monospace; background: #fafafa; }\n");
+    for cell in Cell::ALL {
+        writeln!(
+            output,
+            ".{} {{ color: {}; }}",
+            cell.class_name(),
+            cell.html_color()
+        )
+        .unwrap();
+    }
+    output.push_str("</style>\n</head>\n<body>\n<pre>\n");
+
+    for row in rows {
+        for (cell, text) in runs(row) {
+            write!(
+                output,
+                "<span class=\"{}\">{}</span>",
+                cell.class_name(),
+                escape_html(&text)
+            )
+            .unwrap();
+        }
+        output.push('\n');
+    }
+
+    output.push_str("</pre>\n<ul>\n");
+    for (cell, line) in legend.lines() {
+        writeln!(
+            output,
+            "<li><span class=\"{}\">■</span> {line}</li>",
+            cell.class_name()
+        )
+        .unwrap();
+    }
+    output.push_str("</ul>\n</body>\n</html>\n");
+    output
+}
+
+fn escape_html(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                _ => escaped.push(c),
+            }
+            escaped
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ansi_example() {
+        let input = include_str!("../example.txt");
+        let output = render(input, &GearRule::default(), Format::Ansi);
+        let first_line = output.lines().next().unwrap();
+        assert_eq!(
+            first_line,
+            "\x1b[32m467\x1b[0m\x1b[2m..\x1b[0m\x1b[31m114\x1b[0m\x1b[2m..\x1b[0m"
+        );
+        assert!(output.contains("\x1b[1;35m*\x1b[0m"));
+        assert!(output.contains("part numbers: 8 (sum 4361)"));
+        assert!(output.contains("non-part numbers: 2 (sum 172)"));
+        assert!(output.contains("symbols: 6"));
+        assert!(output.contains("gears: 2 (ratio sum 467835)"));
+    }
+
+    #[test]
+    fn html_example() {
+        let input = "1&.\n...";
+        let output = render(input, &GearRule::default(), Format::Html);
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains(
+            "<span class=\"part\">1</span><span class=\"symbol\">&amp;</span><span class=\"empty\">.</span>"
+        ));
+        assert!(output.contains("part numbers: 1 (sum 1)"));
+        assert!(output.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn format_from_str() {
+        assert_eq!(Format::try_from("html"), Ok(Format::Html));
+        assert!(Format::try_from("svg").is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::gear::GearRule;
+use std::collections::HashMap;
+
+// Every cell outside the grid, including cells past the end of a row that is shorter than its
+// neighbours, is treated as '.', so numbers and symbols on an edge or next to a ragged row are
+// matched exactly like interior ones. Columns are counted in chars.
+pub struct Schematic {
+    numbers: Vec<SchematicNumber>,
+    symbols: Vec<Symbol>,
+    number_locations: HashMap<Position, usize>,
+    number_symbols: Vec<Vec<usize>>,
+    symbol_numbers: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    pub fn new(input: &str) -> Self {
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        let mut number_locations = HashMap::new();
+
+        for (y, line) in input.lines().enumerate() {
+            let mut num: Option<SchematicNumber> = None;
+
+            for (x, c) in line.chars().enumerate() {
+                if let Some(digit) = c.to_digit(10) {
+                    let num = num.get_or_insert(SchematicNumber::new(0, y, x, x));
+                    num.value = num.value * 10 + digit as i64;
+                    num.end = x;
+                    number_locations.insert(Position::new(x, y), numbers.len());
+                    continue;
+                }
+
+                if let Some(num) = num.take() {
+                    numbers.push(num);
+                }
+
+                if c != '.' {
+                    symbols.push(Symbol::new(c, Position::new(x, y)));
+                }
+            }
+
+            if let Some(num) = num {
+                numbers.push(num);
+            }
+        }
+
+        let mut number_symbols = vec![vec![]; numbers.len()];
+        let symbol_numbers = symbols
+            .iter()
+            .enumerate()
+            .map(|(symbol_index, symbol)| {
+                let mut adjacent: Vec<usize> = symbol
+                    .position
+                    .neighbors()
+                    .filter_map(|pos| number_locations.get(&pos).copied())
+                    .collect();
+                adjacent.sort_unstable();
+                adjacent.dedup();
+
+                for &number_index in adjacent.iter() {
+                    number_symbols[number_index].push(symbol_index);
+                }
+                adjacent
+            })
+            .collect();
+
+        Self {
+            numbers,
+            symbols,
+            number_locations,
+            number_symbols,
+            symbol_numbers,
+        }
+    }
+
+    pub fn numbers(&self) -> &[SchematicNumber] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn number_index_at(&self, pos: Position) -> Option<usize> {
+        self.number_locations.get(&pos).copied()
+    }
+
+    pub fn adjacent_numbers(&self, symbol_index: usize) -> impl Iterator<Item = &SchematicNumber> {
+        self.symbol_numbers[symbol_index]
+            .iter()
+            .map(|&number_index| &self.numbers[number_index])
+    }
+
+    pub fn adjacent_number_indices(&self, symbol_index: usize) -> &[usize] {
+        &self.symbol_numbers[symbol_index]
+    }
+
+    pub fn adjacent_symbols(&self, number_index: usize) -> impl Iterator<Item = &Symbol> {
+        self.number_symbols[number_index]
+            .iter()
+            .map(|&symbol_index| &self.symbols[symbol_index])
+    }
+
+    pub fn is_part_number(&self, number_index: usize) -> bool {
+        !self.number_symbols[number_index].is_empty()
+    }
+
+    pub fn part_numbers(&self) -> impl Iterator<Item = &SchematicNumber> {
+        self.numbers
+            .iter()
+            .enumerate()
+            .filter(|&(number_index, _)| self.is_part_number(number_index))
+            .map(|(_, num)| num)
+    }
+
+    pub fn part_number_sum(&self) -> i64 {
+        self.part_numbers().map(|num| num.value).sum()
+    }
+
+    pub fn gear_ratio_sum(&self) -> i64 {
+        self.gear_total(&GearRule::default())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SchematicNumber {
+    pub value: i64,
+    pub y: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl SchematicNumber {
+    fn new(value: i64, y: usize, start: usize, end: usize) -> Self {
+        Self {
+            value,
+            y,
+            start,
+            end,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Symbol {
+    pub value: char,
+    pub position: Position,
+}
+
+impl Symbol {
+    fn new(value: char, position: Position) -> Self {
+        Self { value, position }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Position {
+    pub fn new(x: usize, y: usize) -> Self {
+        Position { x, y }
+    }
+
+    fn neighbors(self) -> impl Iterator<Item = Position> {
+        let offsets = [
+            (-1, -1), // top left
+            (0, -1),  // top
+            (1, -1),  // top right
+            (1, 0),   // right
+            (1, 1),   // bottom right
+            (0, 1),   // bottom
+            (-1, 1),  // bottom left
+            (-1, 0),  // left
+        ];
+
+        offsets.into_iter().filter_map(move |(x_offset, y_offset)| {
+            let x = self.x.checked_add_signed(x_offset)?;
+            let y = self.y.checked_add_signed(y_offset)?;
+            Some(Position::new(x, y))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_numbers_and_symbols() {
+        let schematic = Schematic::new(include_str!("../example.txt"));
+        assert_eq!(schematic.numbers().len(), 10);
+        assert_eq!(schematic.symbols().len(), 6);
+        assert_eq!(schematic.numbers()[0], SchematicNumber::new(467, 0, 0, 2));
+        assert_eq!(
+            schematic.symbols()[0],
+            Symbol::new('*', Position::new(3, 1))
+        );
+
+        let adjacent: Vec<i64> = schematic.adjacent_numbers(0).map(|num| num.value).collect();
+        assert_eq!(adjacent, vec![467, 35]);
+    }
+
+    #[test]
+    fn non_part_numbers_example() {
+        let schematic = Schematic::new(include_str!("../example.txt"));
+        let non_part_numbers: Vec<i64> = schematic
+            .numbers()
+            .iter()
+            .enumerate()
+            .filter(|&(number_index, _)| !schematic.is_part_number(number_index))
+            .map(|(_, num)| num.value)
+            .collect();
+        assert_eq!(non_part_numbers, vec![114, 58]);
+    }
+}
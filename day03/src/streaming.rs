@@ -0,0 +1,141 @@
+use std::io::{self, BufRead};
+
+pub fn solve<R: BufRead>(reader: R) -> io::Result<Totals> {
+    let mut totals = Totals::default();
+    let mut lines = reader.lines();
+
+    let mut prev: Option<Row> = None;
+    let Some(mut cur) = lines.next().transpose()?.map(Row::new) else {
+        return Ok(totals);
+    };
+
+    loop {
+        let next = lines.next().transpose()?.map(Row::new);
+        totals.add_row(prev.as_ref(), &cur, next.as_ref());
+
+        match next {
+            Some(next) => prev = Some(std::mem::replace(&mut cur, next)),
+            None => break Ok(totals),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub part_number_sum: i64,
+    pub gear_ratio_sum: i64,
+}
+
+impl Totals {
+    fn add_row(&mut self, prev: Option<&Row>, cur: &Row, next: Option<&Row>) {
+        let window = [prev, Some(cur), next];
+
+        for num in cur.numbers.iter() {
+            let is_part_number = window
+                .iter()
+                .flatten()
+                .any(|row| (num.start.saturating_sub(1)..=num.end + 1).any(|x| row.is_symbol(x)));
+            if is_part_number {
+                self.part_number_sum += num.value;
+            }
+        }
+
+        for (x, _) in cur.cells.iter().enumerate().filter(|(_, &c)| c == '*') {
+            let mut adjacent = window
+                .iter()
+                .flatten()
+                .flat_map(|row| row.numbers.iter())
+                .filter(|num| num.start <= x + 1 && x <= num.end + 1);
+
+            if let (Some(n1), Some(n2), None) = (adjacent.next(), adjacent.next(), adjacent.next())
+            {
+                self.gear_ratio_sum += n1.value * n2.value;
+            }
+        }
+    }
+}
+
+struct Row {
+    cells: Vec<char>,
+    numbers: Vec<RowNumber>,
+}
+
+impl Row {
+    fn new(line: String) -> Self {
+        let cells: Vec<char> = line.chars().collect();
+        let mut numbers: Vec<RowNumber> = vec![];
+
+        for (x, &c) in cells.iter().enumerate() {
+            let Some(digit) = c.to_digit(10) else {
+                continue;
+            };
+
+            let digit = digit as i64;
+            match numbers.last_mut() {
+                Some(num) if num.end + 1 == x => {
+                    num.value = num.value * 10 + digit;
+                    num.end = x;
+                }
+                _ => numbers.push(RowNumber {
+                    value: digit,
+                    start: x,
+                    end: x,
+                }),
+            }
+        }
+
+        Self { cells, numbers }
+    }
+
+    fn is_symbol(&self, x: usize) -> bool {
+        matches!(self.cells.get(x), Some(&c) if !c.is_ascii_digit() && c != '.')
+    }
+}
+
+struct RowNumber {
+    value: i64,
+    start: usize,
+    end: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaming_example() {
+        let input = include_str!("../example.txt");
+        let output = solve(input.as_bytes()).unwrap();
+        let expected = Totals {
+            part_number_sum: 4361,
+            gear_ratio_sum: 467835,
+        };
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn streaming_input() {
+        let input = include_str!("../input.txt");
+        let output = solve(input.as_bytes()).unwrap();
+        let expected = Totals {
+            part_number_sum: 538046,
+            gear_ratio_sum: 81709807,
+        };
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn streaming_counts_columns_in_chars() {
+        let output = solve("é12\n..*\n.3.".as_bytes()).unwrap();
+        let expected = Totals {
+            part_number_sum: 15,
+            gear_ratio_sum: 36,
+        };
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn streaming_empty() {
+        assert_eq!(solve("".as_bytes()).unwrap(), Totals::default());
+    }
+}
@@ -1,3 +1,5 @@
+use std::{collections::HashSet, fmt::Write, sync::OnceLock};
+
 fn main() {
     divan::main();
 }
@@ -13,3 +15,69 @@ fn part2() {
     let input = include_str!("../input.txt");
     day04::part2(divan::black_box(input));
 }
+
+#[divan::bench]
+fn part1_generated() {
+    day04::part1(divan::black_box(generated_input()));
+}
+
+#[divan::bench]
+fn matches_bitset_generated() -> u32 {
+    day04::card::parse_cards(divan::black_box(generated_input()))
+        .map(|card| card.unwrap().matches())
+        .sum()
+}
+
+#[divan::bench]
+fn matches_hashset_generated() -> usize {
+    divan::black_box(generated_input())
+        .lines()
+        .map(|line| {
+            let mut card_parts = line.split(':').nth(1).unwrap().split('|');
+            let winning_nums = HashSet::<i32>::from_iter(
+                card_parts
+                    .next()
+                    .unwrap()
+                    .split(' ')
+                    .filter_map(|s| s.parse::<i32>().ok()),
+            );
+
+            card_parts
+                .next()
+                .unwrap()
+                .split(' ')
+                .filter_map(|s| s.parse::<i32>().ok())
+                .filter(|n| winning_nums.contains(n))
+                .count()
+        })
+        .sum()
+}
+
+const GENERATED_CARDS: u32 = 1_000_000;
+
+fn generated_input() -> &'static str {
+    static INPUT: OnceLock<String> = OnceLock::new();
+    INPUT.get_or_init(|| {
+        let mut seed: u64 = 0x2023_1204;
+        let mut next_num = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % 99 + 1
+        };
+
+        let mut input = String::new();
+        for id in 1..=GENERATED_CARDS {
+            write!(input, "Card {id:>7}:").unwrap();
+            for _ in 0..10 {
+                write!(input, " {:>2}", next_num()).unwrap();
+            }
+            input.push_str(" |");
+            for _ in 0..25 {
+                write!(input, " {:>2}", next_num()).unwrap();
+            }
+            input.push('\n');
+        }
+        input
+    })
+}
@@ -0,0 +1,34 @@
+use day04::{
+    copies::{copy_table, total_copies},
+    scoring::{total_points, CopyRule, PointsRule},
+};
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+fn main() {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    let mut args = std::env::args().skip(1);
+    let points_rule = args
+        .next()
+        .map_or(Ok(PointsRule::Doubling), |rule| {
+            PointsRule::try_from(rule.as_str())
+        })
+        .unwrap();
+    let copy_rule = args
+        .next()
+        .map_or(Ok(CopyRule::Truncated), |rule| {
+            CopyRule::try_from(rule.as_str())
+        })
+        .unwrap();
+
+    let input = include_str!("../../input.txt");
+    println!("{}", total_points(input, points_rule).unwrap());
+
+    let table = copy_table(input, copy_rule).unwrap();
+    let total = total_copies(&table).expect("total copies should not overflow");
+    println!("{total}");
+}
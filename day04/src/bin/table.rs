@@ -0,0 +1,24 @@
+use day04::{
+    copies::{copy_table, format_table},
+    scoring::CopyRule,
+};
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+fn main() {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    let rule = std::env::args()
+        .nth(1)
+        .map_or(Ok(CopyRule::Truncated), |rule| {
+            CopyRule::try_from(rule.as_str())
+        })
+        .unwrap();
+
+    let input = include_str!("../../input.txt");
+    let table = copy_table(input, rule).unwrap();
+    print!("{}", format_table(&table));
+}
@@ -0,0 +1,133 @@
+pub fn parse_cards(input: &str) -> impl Iterator<Item = Result<Card, String>> + '_ {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(Card::try_from)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: NumberSet,
+    pub have: NumberSet,
+}
+
+impl Card {
+    pub fn matches(&self) -> u32 {
+        self.winning.intersection_len(&self.have)
+    }
+}
+
+impl TryFrom<&str> for Card {
+    type Error = String;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let bytes = line.as_bytes();
+        let invalid = || format!("\"{line}\" is not a valid card.");
+
+        let rest = bytes.strip_prefix(b"Card").ok_or_else(invalid)?;
+        let colon = rest.iter().position(|&b| b == b':').ok_or_else(invalid)?;
+        let bar = rest.iter().position(|&b| b == b'|').ok_or_else(invalid)?;
+        if bar < colon {
+            return Err(invalid());
+        }
+
+        let id = parse_nums(&rest[..colon])
+            .try_fold(None, |id, n| match (id, n) {
+                (None, Some(n)) => Ok(Some(n)),
+                _ => Err(invalid()),
+            })?
+            .ok_or_else(invalid)?;
+
+        let to_set = |nums: &[u8]| {
+            parse_nums(nums).try_fold(NumberSet::default(), |mut set, n| {
+                let n = n
+                    .filter(|&n| n <= NumberSet::MAX as u32)
+                    .ok_or_else(invalid)?;
+                set.insert(n as u8);
+                Ok::<_, String>(set)
+            })
+        };
+
+        Ok(Self {
+            id,
+            winning: to_set(&rest[colon + 1..bar])?,
+            have: to_set(&rest[bar + 1..])?,
+        })
+    }
+}
+
+// Yields every space separated number, or `None` for a token that is not all digits.
+fn parse_nums(bytes: &[u8]) -> impl Iterator<Item = Option<u32>> + '_ {
+    bytes
+        .split(|&b| b == b' ')
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token.iter().try_fold(0u32, |n, &b| {
+                b.is_ascii_digit()
+                    .then(|| n.checked_mul(10)?.checked_add((b - b'0') as u32))?
+            })
+        })
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NumberSet {
+    low: u128,
+    high: u128,
+}
+
+impl NumberSet {
+    pub const MAX: u8 = u8::MAX;
+
+    pub fn insert(&mut self, n: u8) {
+        if n < 128 {
+            self.low |= 1 << n;
+        } else {
+            self.high |= 1 << (n - 128);
+        }
+    }
+
+    pub fn contains(&self, n: u8) -> bool {
+        if n < 128 {
+            self.low & (1 << n) != 0
+        } else {
+            self.high & (1 << (n - 128)) != 0
+        }
+    }
+
+    pub fn intersection_len(&self, other: &Self) -> u32 {
+        (self.low & other.low).count_ones() + (self.high & other.high).count_ones()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_card() {
+        let card = Card::try_from("Card   1: 41 48  3 | 83 48  3 200").unwrap();
+        assert_eq!(card.id, 1);
+        assert!(card.winning.contains(41));
+        assert!(card.winning.contains(3));
+        assert!(card.have.contains(200));
+        assert!(!card.have.contains(41));
+        assert_eq!(card.matches(), 2);
+    }
+
+    #[test]
+    fn matches_example() {
+        let matches: Vec<u32> = parse_cards(include_str!("../example.txt"))
+            .map(|card| card.unwrap().matches())
+            .collect();
+        assert_eq!(matches, vec![4, 2, 2, 1, 0, 0]);
+    }
+
+    #[test]
+    fn rejects_invalid_cards() {
+        assert!(Card::try_from("Card 1: 1 2 3").is_err());
+        assert!(Card::try_from("Card 1: 1 x | 3").is_err());
+        assert!(Card::try_from("Card 1: 256 | 3").is_err());
+        assert!(Card::try_from("Card: 1 | 3").is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{card::parse_cards, scoring::CopyRule};
+use std::fmt::Write;
+
+pub fn copy_table(input: &str, rule: CopyRule) -> Result<Vec<CardCopies>, String> {
+    let mut table: Vec<CardCopies> = parse_cards(input)
+        .map(|card| {
+            card.map(|card| CardCopies {
+                card: card.id,
+                matches: card.matches(),
+                copies: 1,
+                granted_to: vec![],
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    for i in 0..table.len() {
+        let copies = table[i].copies;
+        let targets = rule.targets(i, table[i].matches, table.len());
+
+        for &target in targets.iter() {
+            let row = &mut table[target];
+            row.copies = row
+                .copies
+                .checked_add(copies)
+                .ok_or_else(|| format!("copies overflowed at card {}", row.card))?;
+        }
+        table[i].granted_to = targets.iter().map(|&target| table[target].card).collect();
+    }
+
+    Ok(table)
+}
+
+pub fn total_copies(table: &[CardCopies]) -> Option<u64> {
+    table
+        .iter()
+        .try_fold(0u64, |total, row| total.checked_add(row.copies))
+}
+
+pub fn format_table(table: &[CardCopies]) -> String {
+    let mut output = String::new();
+    writeln!(
+        output,
+        "{:>6} {:>7} {:>12}  granted to",
+        "card", "matches", "copies"
+    )
+    .unwrap();
+
+    for row in table {
+        let granted_to = format_cards(&row.granted_to);
+        writeln!(
+            output,
+            "{:>6} {:>7} {:>12}  {} x{}",
+            row.card, row.matches, row.copies, granted_to, row.copies
+        )
+        .unwrap();
+    }
+
+    match total_copies(table) {
+        Some(total) => writeln!(output, "{:>6} {:>7} {:>12}", "total", "", total).unwrap(),
+        None => writeln!(output, "{:>6} {:>7} {:>12}", "total", "", "overflow").unwrap(),
+    }
+    output
+}
+
+fn format_cards(cards: &[u32]) -> String {
+    if cards.is_empty() {
+        return "-".to_string();
+    }
+
+    cards
+        .chunk_by(|a, b| a + 1 == *b)
+        .map(|run| match run {
+            [card] => card.to_string(),
+            [first, .., last] => format!("{first}-{last}"),
+            [] => unreachable!("chunks should never be empty"),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CardCopies {
+    pub card: u32,
+    pub matches: u32,
+    pub copies: u64,
+    pub granted_to: Vec<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_table_example() {
+        let table = copy_table(include_str!("../example.txt"), CopyRule::Truncated).unwrap();
+        let copies: Vec<u64> = table.iter().map(|row| row.copies).collect();
+        assert_eq!(copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(table[0].granted_to, vec![2, 3, 4, 5]);
+        assert_eq!(table[3].granted_to, vec![5]);
+        assert!(table[5].granted_to.is_empty());
+    }
+
+    #[test]
+    fn total_copies_overflow() {
+        let row = |copies| CardCopies {
+            card: 1,
+            matches: 0,
+            copies,
+            granted_to: vec![],
+        };
+        assert_eq!(total_copies(&[row(1), row(2)]), Some(3));
+        assert_eq!(total_copies(&[row(u64::MAX), row(1)]), None);
+    }
+
+    #[test]
+    fn copy_table_truncates_at_last_card() {
+        let table = copy_table("Card 1: 1 2 | 1 2\nCard 2: 3 | 3", CopyRule::Truncated).unwrap();
+        assert_eq!(table[0].granted_to, vec![2]);
+        assert_eq!(table[1].granted_to, Vec::<u32>::new());
+        assert_eq!(table[1].copies, 2);
+    }
+
+    #[test]
+    fn format_table_example() {
+        let output =
+            format_table(&copy_table(include_str!("../example.txt"), CopyRule::Truncated).unwrap());
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[1], "     1       4            1  2-5 x1");
+        assert_eq!(lines[4], "     4       1            8  5 x8");
+        assert_eq!(lines[6], "     6       0            1  - x1");
+        assert_eq!(lines[7], " total                   30");
+    }
+}
@@ -1,5 +1,8 @@
+pub mod card;
+pub mod copies;
 mod part1;
 mod part2;
+pub mod scoring;
 
 pub use part1::part1;
 pub use part2::part2;
@@ -1,28 +1,8 @@
-use std::collections::HashSet;
+use crate::scoring::{total_points, PointsRule};
 
 pub fn part1(input: &str) -> i32 {
-    input
-        .lines()
-        .map(|line| {
-            let line_parts = line.split(':');
-            let mut card_parts = line_parts.last().unwrap().split('|');
-            let winning_nums = HashSet::<i32>::from_iter(
-                card_parts
-                    .next()
-                    .unwrap()
-                    .split(' ')
-                    .filter_map(|s| s.parse::<i32>().ok()),
-            );
-
-            card_parts
-                .next()
-                .unwrap()
-                .split(' ')
-                .filter_map(|s| s.parse::<i32>().ok())
-                .filter(|n| winning_nums.contains(n))
-                .fold(0, |acc, _| if acc == 0 { 1 } else { acc * 2 })
-        })
-        .sum()
+    let points = total_points(input, PointsRule::Doubling).expect("points should not overflow");
+    i32::try_from(points).expect("points should fit in an i32")
 }
 
 #[cfg(test)]
@@ -44,4 +24,11 @@ mod tests {
         let expected = 24706;
         assert_eq!(output, expected);
     }
+
+    #[test]
+    #[should_panic(expected = "points should fit in an i32")]
+    fn part1_points_beyond_i32() {
+        let numbers: Vec<String> = (1..=32).map(|n| n.to_string()).collect();
+        part1(&format!("Card 1: {0} | {0}", numbers.join(" ")));
+    }
 }
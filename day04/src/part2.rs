@@ -1,50 +1,12 @@
-use std::{
-    cell::RefCell,
-    collections::{HashMap, HashSet},
+use crate::{
+    copies::{copy_table, total_copies},
+    scoring::CopyRule,
 };
 
 pub fn part2(input: &str) -> i32 {
-    let card_copy_map = RefCell::new(HashMap::new());
-
-    input
-        .lines()
-        .map(|line| {
-            let mut line_parts = line.split(':');
-            let card_num = line_parts
-                .next()
-                .unwrap()
-                .split(' ')
-                .last()
-                .unwrap()
-                .parse::<i32>()
-                .unwrap();
-            let mut card_parts = line_parts.last().unwrap().split('|');
-            let winning_nums = HashSet::<i32>::from_iter(
-                card_parts
-                    .next()
-                    .unwrap()
-                    .split(' ')
-                    .filter_map(|s| s.parse::<i32>().ok()),
-            );
-
-            let copies_won = card_parts
-                .next()
-                .unwrap()
-                .split(' ')
-                .filter_map(|s| s.parse::<i32>().ok())
-                .filter(|n| winning_nums.contains(n))
-                .count() as i32;
-
-            let current_num_copies = card_copy_map.borrow().get(&card_num).copied().unwrap_or(1);
-
-            for i in 1..=copies_won {
-                *card_copy_map.borrow_mut().entry(card_num + i).or_insert(1) += current_num_copies;
-            }
-
-            card_copy_map.borrow_mut().remove(&card_num);
-            current_num_copies
-        })
-        .sum()
+    let table = copy_table(input, CopyRule::Truncated).expect("copies should not overflow");
+    let copies = total_copies(&table).expect("total copies should not overflow");
+    i32::try_from(copies).expect("total copies should fit in an i32")
 }
 
 #[cfg(test)]
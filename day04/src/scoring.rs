@@ -0,0 +1,185 @@
+use crate::card::parse_cards;
+
+pub fn total_points(input: &str, rule: PointsRule) -> Result<u64, String> {
+    parse_cards(input).try_fold(0u64, |total, card| {
+        let card = card?;
+        rule.points(card.matches())
+            .and_then(|points| total.checked_add(points))
+            .ok_or_else(|| format!("points overflowed at card {}", card.id))
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointsRule {
+    Doubling,
+    Linear,
+}
+
+impl PointsRule {
+    pub fn points(&self, matches: u32) -> Option<u64> {
+        match (self, matches) {
+            (_, 0) => Some(0),
+            (PointsRule::Doubling, matches) => 1u64.checked_shl(matches - 1),
+            (PointsRule::Linear, matches) => Some(matches as u64),
+        }
+    }
+}
+
+impl TryFrom<&str> for PointsRule {
+    type Error = String;
+
+    fn try_from(rule: &str) -> Result<Self, Self::Error> {
+        Ok(match rule {
+            "doubling" => PointsRule::Doubling,
+            "linear" => PointsRule::Linear,
+            _ => return Err(format!("\"{rule}\" is not a valid points rule.")),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CopyRule {
+    Truncated,
+    Capped(u32),
+    Wrapping,
+}
+
+impl CopyRule {
+    // Indices of the cards that receive a copy from every copy held of card `index`. With
+    // `Wrapping`, copies granted to cards that were already processed are counted but do not
+    // cascade any further, and a card never wins a copy of itself, so at most every other card
+    // receives one copy.
+    pub fn targets(&self, index: usize, matches: u32, card_count: usize) -> Vec<usize> {
+        let matches = matches as usize;
+        match *self {
+            CopyRule::Truncated => (index + 1..=index + matches)
+                .take_while(|&target| target < card_count)
+                .collect(),
+            CopyRule::Capped(cap) => (index + 1..=index + matches.min(cap as usize))
+                .take_while(|&target| target < card_count)
+                .collect(),
+            CopyRule::Wrapping => (1..=matches.min(card_count.saturating_sub(1)))
+                .map(|offset| (index + offset) % card_count)
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<&str> for CopyRule {
+    type Error = String;
+
+    fn try_from(rule: &str) -> Result<Self, Self::Error> {
+        Ok(match rule.split_once(':') {
+            None if rule == "truncated" => CopyRule::Truncated,
+            None if rule == "wrapping" => CopyRule::Wrapping,
+            Some(("capped", cap)) => match cap.parse() {
+                Ok(cap) => CopyRule::Capped(cap),
+                Err(_) => return Err(format!("\"{cap}\" is not a valid copy cap.")),
+            },
+            _ => return Err(format!("\"{rule}\" is not a valid copy rule.")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::copies::{copy_table, total_copies};
+
+    #[test]
+    fn points_rules_example() {
+        let input = include_str!("../example.txt");
+        assert_eq!(total_points(input, PointsRule::Doubling), Ok(13));
+        assert_eq!(total_points(input, PointsRule::Linear), Ok(9));
+    }
+
+    #[test]
+    fn doubling_points_overflow() {
+        assert_eq!(PointsRule::Doubling.points(64), Some(1 << 63));
+        assert_eq!(PointsRule::Doubling.points(65), None);
+
+        let winning: Vec<String> = (0..70).map(|n| n.to_string()).collect();
+        let line = format!("Card 1: {0} | {0}", winning.join(" "));
+        assert!(total_points(&line, PointsRule::Doubling).is_err());
+        assert_eq!(total_points(&line, PointsRule::Linear), Ok(70));
+    }
+
+    #[test]
+    fn invalid_cards_are_errors() {
+        let input = "Card 1: 1 x | 1";
+        assert_eq!(
+            total_points(input, PointsRule::Linear),
+            Err("\"Card 1: 1 x | 1\" is not a valid card.".to_string())
+        );
+        assert!(copy_table(input, CopyRule::Truncated).is_err());
+    }
+
+    #[test]
+    fn copy_rules_example() {
+        let input = include_str!("../example.txt");
+        assert_eq!(
+            total_copies(&copy_table(input, CopyRule::Truncated).unwrap()),
+            Some(30)
+        );
+        assert_eq!(
+            total_copies(&copy_table(input, CopyRule::Capped(4)).unwrap()),
+            Some(30)
+        );
+        assert_eq!(
+            total_copies(&copy_table(input, CopyRule::Capped(1)).unwrap()),
+            Some(1 + 2 + 3 + 4 + 5 + 1)
+        );
+        assert_eq!(
+            total_copies(&copy_table(input, CopyRule::Capped(0)).unwrap()),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn wrapping_copies() {
+        let input = "Card 1: 1 | 2\nCard 2: 1 | 2\nCard 3: 1 2 | 1 2";
+        assert_eq!(CopyRule::Wrapping.targets(2, 2, 3), vec![0, 1]);
+        let table = copy_table(input, CopyRule::Wrapping).unwrap();
+        let copies: Vec<u64> = table.iter().map(|row| row.copies).collect();
+        assert_eq!(copies, vec![2, 2, 1]);
+        assert_eq!(table[2].granted_to, vec![1, 2]);
+        assert_eq!(
+            copy_table(input, CopyRule::Truncated).unwrap()[2].granted_to,
+            Vec::<u32>::new()
+        );
+    }
+
+    #[test]
+    fn wrapping_skips_the_winning_card() {
+        assert_eq!(CopyRule::Wrapping.targets(0, 3, 2), vec![1]);
+        assert_eq!(CopyRule::Wrapping.targets(1, 5, 4), vec![2, 3, 0]);
+
+        let table = copy_table("Card 1: 1 | 1", CopyRule::Wrapping).unwrap();
+        assert_eq!(table[0].copies, 1);
+        assert_eq!(table[0].granted_to, Vec::<u32>::new());
+
+        let input = "Card 1: 1 2 3 | 1 2 3\nCard 2: 1 | 2";
+        let table = copy_table(input, CopyRule::Wrapping).unwrap();
+        assert_eq!(table[0].granted_to, vec![2]);
+        let copies: Vec<u64> = table.iter().map(|row| row.copies).collect();
+        assert_eq!(copies, vec![1, 2]);
+    }
+
+    #[test]
+    fn copies_overflow() {
+        let input: String = (1..=100)
+            .map(|id| format!("Card {id}: 1 2 | 1 2\n"))
+            .collect();
+        let result = copy_table(&input, CopyRule::Truncated);
+        assert!(result.is_err(), "{result:?}");
+    }
+
+    #[test]
+    fn rules_from_str() {
+        assert_eq!(PointsRule::try_from("linear"), Ok(PointsRule::Linear));
+        assert_eq!(CopyRule::try_from("capped:3"), Ok(CopyRule::Capped(3)));
+        assert_eq!(CopyRule::try_from("wrapping"), Ok(CopyRule::Wrapping));
+        assert!(CopyRule::try_from("capped:x").is_err());
+        assert!(CopyRule::try_from("all").is_err());
+    }
+}
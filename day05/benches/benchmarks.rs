@@ -13,3 +13,29 @@ fn part2() {
     let input = include_str!("../input.txt");
     day05::part2(divan::black_box(input));
 }
+
+#[divan::bench]
+fn seed_queries_folded(bencher: divan::Bencher) {
+    let input = include_str!("../input.txt");
+    let almanac = day05::almanac::Almanac::try_from(input).unwrap();
+    let chain = almanac.chain("seed", "location").unwrap();
+    bencher.bench_local(|| {
+        (0..10_000_000_i64)
+            .step_by(1_000)
+            .map(|seed| chain.map(divan::black_box(seed)))
+            .min()
+    });
+}
+
+#[divan::bench]
+fn seed_queries_composed(bencher: divan::Bencher) {
+    let input = include_str!("../input.txt");
+    let almanac = day05::almanac::Almanac::try_from(input).unwrap();
+    let seed_to_location = almanac.chain("seed", "location").unwrap().compose();
+    bencher.bench_local(|| {
+        (0..10_000_000_i64)
+            .step_by(1_000)
+            .map(|seed| seed_to_location.map(divan::black_box(seed)))
+            .min()
+    });
+}
@@ -0,0 +1,494 @@
+use crate::interval_map::{merge_ranges, IntervalMap, Priority, Range, Segment};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    maps: HashMap<String, CategoryMap>,
+}
+
+impl Almanac {
+    // Reads the seeds as (start, length) pairs.
+    pub fn seed_ranges(&self) -> Result<Vec<Range>, String> {
+        let pairs = self.seeds.chunks_exact(2);
+        if !pairs.remainder().is_empty() {
+            return Err(format!(
+                "line 1: {} seeds cannot be read as start and length pairs.",
+                self.seeds.len()
+            ));
+        }
+
+        Ok(pairs
+            .map(|seed_range| Range::from_len(seed_range[0], seed_range[1]))
+            .collect())
+    }
+
+    pub fn maps(&self) -> impl Iterator<Item = &CategoryMap> {
+        self.maps.values()
+    }
+
+    pub fn chain(&self, from: &str, to: &str) -> Result<Chain<'_>, String> {
+        let mut maps = vec![];
+        let mut visited = HashSet::from([from]);
+        let mut category = from;
+
+        while category != to {
+            let map = self
+                .maps
+                .get(category)
+                .ok_or_else(|| format!("no map from \"{category}\" on the way to \"{to}\"."))?;
+            if !visited.insert(&map.destination) {
+                return Err(format!(
+                    "maps from \"{from}\" form a cycle at \"{}\".",
+                    map.destination
+                ));
+            }
+
+            maps.push(&map.map);
+            category = &map.destination;
+        }
+
+        Ok(Chain { maps })
+    }
+}
+
+impl Almanac {
+    pub fn parse(input: &str, overlaps: Overlaps) -> Result<Self, String> {
+        let (seeds, sections) = parse_sections(input)?;
+
+        if overlaps == Overlaps::Reject {
+            let issues = find_issues(&sections);
+            if !issues.is_empty() {
+                let issues: Vec<String> = issues.iter().map(Issue::to_string).collect();
+                return Err(issues.join("\n"));
+            }
+        }
+
+        let mut maps = HashMap::new();
+        for section in sections {
+            let segments = section.segments.into_iter().map(|(_, segment)| segment);
+            let map = match overlaps {
+                Overlaps::Reject => IntervalMap::new(segments)
+                    .map_err(|err| format!("line {}: {err}", section.line))?,
+                Overlaps::Normalize(priority) => IntervalMap::normalized(segments, priority),
+            };
+            let category_map = CategoryMap {
+                source: section.source.to_string(),
+                destination: section.destination.to_string(),
+                map,
+            };
+
+            if maps
+                .insert(section.source.to_string(), category_map)
+                .is_some()
+            {
+                return Err(format!(
+                    "line {}: there is already a map from \"{}\".",
+                    section.line, section.source
+                ));
+            }
+        }
+
+        Ok(Self { seeds, maps })
+    }
+
+    pub fn validate(input: &str) -> Result<Vec<Issue>, String> {
+        let (_, sections) = parse_sections(input)?;
+        Ok(find_issues(&sections))
+    }
+}
+
+impl TryFrom<&str> for Almanac {
+    type Error = String;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Almanac::parse(input, Overlaps::Reject)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overlaps {
+    Reject,
+    Normalize(Priority),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Issue {
+    Empty { line: usize },
+    Overlap { line: usize, other_line: usize },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::Empty { line } => write!(f, "line {line}: map range is empty."),
+            Issue::Overlap { line, other_line } => {
+                write!(f, "line {line}: map range overlaps line {other_line}.")
+            }
+        }
+    }
+}
+
+struct Section<'a> {
+    line: usize,
+    source: &'a str,
+    destination: &'a str,
+    segments: Vec<(usize, Segment)>,
+}
+
+fn parse_sections(input: &str) -> Result<(Vec<i64>, Vec<Section<'_>>), String> {
+    let mut lines = input.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+    let seeds = lines
+        .next()
+        .and_then(|(_, line)| line.strip_prefix("seeds:"))
+        .ok_or_else(|| "line 1: almanac should start with seeds.".to_string())?
+        .split_whitespace()
+        .map(|s| {
+            s.parse::<i64>()
+                .map_err(|_| format!("line 1: \"{s}\" is not a valid seed."))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let sections = lines.filter(|(_, line)| !line.is_empty()).try_fold(
+        vec![] as Vec<Section>,
+        |mut sections, (line_number, line)| {
+            if line.starts_with(|c: char| c.is_ascii_digit()) {
+                let segment =
+                    Segment::try_from(line).map_err(|err| format!("line {line_number}: {err}"))?;
+                sections
+                    .last_mut()
+                    .ok_or_else(|| {
+                        format!("line {line_number}: \"{line}\" is not under a map header.")
+                    })?
+                    .segments
+                    .push((line_number, segment));
+            } else {
+                let (source, destination) = line
+                    .strip_suffix(" map:")
+                    .and_then(|categories| categories.split_once("-to-"))
+                    .ok_or_else(|| {
+                        format!("line {line_number}: \"{line}\" is not a valid map header.")
+                    })?;
+                sections.push(Section {
+                    line: line_number,
+                    source,
+                    destination,
+                    segments: vec![],
+                });
+            }
+
+            Ok::<_, String>(sections)
+        },
+    )?;
+
+    Ok((seeds, sections))
+}
+
+fn find_issues(sections: &[Section]) -> Vec<Issue> {
+    let mut issues = vec![];
+
+    for section in sections {
+        let mut segments: Vec<&(usize, Segment)> = section.segments.iter().collect();
+        segments.sort_by_key(|(line, segment)| (segment.source.start, *line));
+
+        let mut furthest: Option<(usize, i64)> = None;
+        for &&(line, segment) in segments.iter() {
+            if segment.source.is_empty() {
+                issues.push(Issue::Empty { line });
+                continue;
+            }
+
+            match furthest {
+                Some((other_line, end)) if segment.source.start <= end => {
+                    issues.push(Issue::Overlap {
+                        line: line.max(other_line),
+                        other_line: line.min(other_line),
+                    });
+                    if segment.source.end > end {
+                        furthest = Some((line, segment.source.end));
+                    }
+                }
+                _ => furthest = Some((line, segment.source.end)),
+            }
+        }
+    }
+
+    issues.sort_by_key(|issue| match *issue {
+        Issue::Empty { line } | Issue::Overlap { line, .. } => line,
+    });
+    issues
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryMap {
+    pub source: String,
+    pub destination: String,
+    pub map: IntervalMap,
+}
+
+#[derive(Debug, Clone)]
+pub struct Chain<'a> {
+    maps: Vec<&'a IntervalMap>,
+}
+
+impl Chain<'_> {
+    pub fn map(&self, value: i64) -> i64 {
+        self.maps.iter().fold(value, |source, map| map.map(source))
+    }
+
+    pub fn map_range(&self, range: Range) -> Vec<Range> {
+        self.maps.iter().fold(vec![range], |source_ranges, map| {
+            source_ranges
+                .into_iter()
+                .flat_map(|source_range| map.map_range(source_range))
+                .collect()
+        })
+    }
+
+    pub fn compose(&self) -> IntervalMap {
+        IntervalMap::compose_all(self.maps.iter().copied())
+    }
+
+    pub fn preimage(&self, range: Range) -> Vec<Range> {
+        self.maps
+            .iter()
+            .rev()
+            .fold(vec![range], |destination_ranges, map| {
+                merge_ranges(
+                    destination_ranges
+                        .into_iter()
+                        .flat_map(|destination_range| map.preimage(destination_range))
+                        .collect(),
+                )
+            })
+    }
+
+    // Walks every destination back to the sources that reach it, keeping track of how far each
+    // source range is shifted on the way, and picks the lowest destination hit by `source_ranges`.
+    pub fn lowest_destination(&self, source_ranges: &[Range]) -> Option<i64> {
+        let all_destinations = Range::new(i64::MIN / 4, i64::MAX / 4);
+        let source_pieces =
+            self.maps
+                .iter()
+                .rev()
+                .fold(vec![(all_destinations, 0)], |destination_pieces, map| {
+                    destination_pieces
+                        .into_iter()
+                        .flat_map(|(destination_range, shift)| {
+                            map.preimage_pieces(destination_range)
+                                .into_iter()
+                                .map(move |(source_range, offset)| (source_range, shift + offset))
+                        })
+                        .collect()
+                });
+
+        source_pieces
+            .iter()
+            .flat_map(|(piece, shift)| {
+                source_ranges
+                    .iter()
+                    .filter_map(move |range| Some(piece.intersect(range)?.start + shift))
+            })
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Almanac {
+        Almanac::try_from(include_str!("../example.txt")).unwrap()
+    }
+
+    #[test]
+    fn parses_example() {
+        let almanac = example();
+        assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(almanac.maps().count(), 7);
+        assert_eq!(
+            almanac.seed_ranges(),
+            Ok(vec![Range::new(79, 92), Range::new(55, 67)])
+        );
+    }
+
+    #[test]
+    fn odd_seed_count() {
+        let almanac = Almanac::try_from("seeds: 1 2 3\n\nseed-to-location map:\n1 2 3\n").unwrap();
+        assert_eq!(almanac.seeds, vec![1, 2, 3]);
+        assert_eq!(
+            almanac.seed_ranges(),
+            Err("line 1: 3 seeds cannot be read as start and length pairs.".to_string())
+        );
+    }
+
+    #[test]
+    fn locations_example() {
+        let almanac = example();
+        let chain = almanac.chain("seed", "location").unwrap();
+        let locations: Vec<i64> = almanac.seeds.iter().map(|&seed| chain.map(seed)).collect();
+        assert_eq!(locations, vec![82, 43, 86, 35]);
+    }
+
+    #[test]
+    fn chains_between_any_categories() {
+        let almanac = example();
+        // seed 79 is soil 81, fertilizer 81, water 81, light 74, temperature 78, humidity 78.
+        assert_eq!(almanac.chain("soil", "humidity").unwrap().map(81), 78);
+        assert_eq!(almanac.chain("light", "light").unwrap().map(74), 74);
+    }
+
+    #[test]
+    fn maps_in_any_order() {
+        let input = "seeds: 79 14\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nseed-to-soil map:\n50 98 2\n52 50 48\n";
+        let almanac = Almanac::try_from(input).unwrap();
+        let chain = almanac.chain("seed", "fertilizer").unwrap();
+        assert_eq!(chain.map(79), 81);
+        assert_eq!(chain.map(14), 53);
+    }
+
+    #[test]
+    fn reports_missing_and_cyclic_links() {
+        let almanac = example();
+        assert!(almanac.chain("location", "seed").is_err());
+        assert!(almanac.chain("seed", "moon").is_err());
+
+        let input = "seeds: 1\n\na-to-b map:\n1 2 3\n\nb-to-a map:\n2 1 3\n";
+        let almanac = Almanac::try_from(input).unwrap();
+        let err = almanac.chain("a", "c").unwrap_err();
+        assert!(err.contains("cycle"), "{err}");
+    }
+
+    #[test]
+    fn reports_invalid_headers() {
+        assert!(Almanac::try_from("seeds: 1\n\nseed to soil map:\n1 2 3").is_err());
+        assert!(Almanac::try_from("seeds: 1\n\na-to-b map:\n1 2 3\n\na-to-c map:\n1 2 3").is_err());
+        assert!(Almanac::try_from("seeds: 1\n\n1 2 3").is_err());
+    }
+
+    #[test]
+    fn validates_example() {
+        assert_eq!(
+            Almanac::validate(include_str!("../example.txt")),
+            Ok(vec![])
+        );
+        assert_eq!(Almanac::validate(include_str!("../input.txt")), Ok(vec![]));
+    }
+
+    const OVERLAPPING: &str =
+        "seeds: 3 7\n\na-to-b map:\n10 0 10\n100 5 10\n0 20 0\n\nb-to-c map:\n0 50 5\n";
+
+    #[test]
+    fn reports_overlapping_and_empty_ranges() {
+        assert_eq!(
+            Almanac::validate(OVERLAPPING),
+            Ok(vec![
+                Issue::Overlap {
+                    line: 5,
+                    other_line: 4
+                },
+                Issue::Empty { line: 6 },
+            ])
+        );
+
+        let err = Almanac::try_from(OVERLAPPING).unwrap_err();
+        assert_eq!(
+            err,
+            "line 5: map range overlaps line 4.\nline 6: map range is empty."
+        );
+    }
+
+    #[test]
+    fn normalizes_with_priority() {
+        let almanac = Almanac::parse(OVERLAPPING, Overlaps::Normalize(Priority::First)).unwrap();
+        let chain = almanac.chain("a", "c").unwrap();
+        assert_eq!(chain.map(3), 13);
+        assert_eq!(chain.map(7), 17);
+
+        let almanac = Almanac::parse(OVERLAPPING, Overlaps::Normalize(Priority::Last)).unwrap();
+        let chain = almanac.chain("a", "c").unwrap();
+        assert_eq!(chain.map(3), 13);
+        assert_eq!(chain.map(7), 102);
+    }
+
+    #[test]
+    fn seeds_for_location_example() {
+        let almanac = example();
+        let chain = almanac.chain("seed", "location").unwrap();
+        let seeds = chain.preimage(Range::new(35, 35));
+        assert!(seeds.iter().any(|range| range.contains(13)));
+        for range in seeds {
+            for seed in range.start..=range.end {
+                assert_eq!(chain.map(seed), 35);
+            }
+        }
+
+        let seeds = chain.preimage(Range::new(0, 99));
+        for seed in 0..100 {
+            let reaches_range = (0..=99).contains(&chain.map(seed));
+            assert_eq!(
+                seeds.iter().any(|range| range.contains(seed)),
+                reaches_range
+            );
+        }
+    }
+
+    #[test]
+    fn lowest_location_example() {
+        let almanac = example();
+        let chain = almanac.chain("seed", "location").unwrap();
+        assert_eq!(
+            chain.lowest_destination(&almanac.seed_ranges().unwrap()),
+            Some(46)
+        );
+        assert_eq!(chain.lowest_destination(&[Range::new(13, 13)]), Some(35));
+        assert_eq!(chain.lowest_destination(&[]), None);
+    }
+
+    #[test]
+    fn lowest_location_input() {
+        let input = include_str!("../input.txt");
+        let almanac = Almanac::try_from(input).unwrap();
+        let chain = almanac.chain("seed", "location").unwrap();
+        assert_eq!(
+            chain.lowest_destination(&almanac.seed_ranges().unwrap()),
+            Some(crate::part2(input))
+        );
+    }
+
+    #[test]
+    fn seed_to_location_example() {
+        let almanac = example();
+        let chain = almanac.chain("seed", "location").unwrap();
+        let seed_to_location = chain.compose();
+        for seed in 0..120 {
+            assert_eq!(seed_to_location.map(seed), chain.map(seed), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn seed_to_location_input() {
+        let almanac = Almanac::try_from(include_str!("../input.txt")).unwrap();
+        let chain = almanac.chain("seed", "location").unwrap();
+        let seed_to_location = chain.compose();
+
+        for &breakpoint in seed_to_location.breakpoints().iter() {
+            for seed in [breakpoint - 1, breakpoint] {
+                assert_eq!(seed_to_location.map(seed), chain.map(seed), "seed {seed}");
+            }
+        }
+
+        let lowest = almanac
+            .seed_ranges()
+            .unwrap()
+            .into_iter()
+            .flat_map(|seed_range| seed_to_location.map_range(seed_range))
+            .map(|location_range| location_range.start)
+            .min();
+        assert_eq!(lowest, Some(31161857));
+    }
+}
@@ -0,0 +1,407 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Range {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Range {
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    pub fn from_len(start: i64, len: i64) -> Self {
+        Self::new(start, start + len - 1)
+    }
+
+    pub fn len(&self) -> i64 {
+        self.end - self.start + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    pub fn intersect(&self, other: &Range) -> Option<Range> {
+        let range = Range::new(self.start.max(other.start), self.end.min(other.end));
+        (!range.is_empty()).then_some(range)
+    }
+
+    pub fn shift(&self, offset: i64) -> Range {
+        Range::new(self.start + offset, self.end + offset)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub source: Range,
+    pub offset: i64,
+}
+
+impl Segment {
+    pub fn new(source: Range, offset: i64) -> Self {
+        Self { source, offset }
+    }
+
+    pub fn destination(&self) -> Range {
+        self.source.shift(self.offset)
+    }
+}
+
+impl TryFrom<&str> for Segment {
+    type Error = String;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let nums = line
+            .split_whitespace()
+            .map(|s| s.parse::<i64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("\"{line}\" is not a valid map range."))?;
+
+        match nums[..] {
+            [destination_start, source_start, len] => Ok(Segment::new(
+                Range::from_len(source_start, len),
+                destination_start - source_start,
+            )),
+            _ => Err(format!("\"{line}\" is not a valid map range.")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalMap {
+    segments: Vec<Segment>,
+}
+
+impl IntervalMap {
+    pub fn new(segments: impl IntoIterator<Item = Segment>) -> Result<Self, String> {
+        let mut segments: Vec<Segment> = segments.into_iter().collect();
+        segments.sort_by_key(|segment| segment.source.start);
+
+        if let Some(segment) = segments.iter().find(|segment| segment.source.is_empty()) {
+            return Err(format!("{segment:?} has zero length."));
+        }
+        if let Some(pair) = segments
+            .windows(2)
+            .find(|pair| pair[0].source.end >= pair[1].source.start)
+        {
+            return Err(format!("{:?} overlaps {:?}.", pair[0], pair[1]));
+        }
+
+        Ok(Self { segments })
+    }
+
+    // Builds a map from segments that may overlap or be empty. Empty segments are dropped and
+    // wherever segments overlap, the one that comes first in `priority` order wins.
+    pub fn normalized(segments: impl IntoIterator<Item = Segment>, priority: Priority) -> Self {
+        let mut segments: Vec<Segment> = segments
+            .into_iter()
+            .filter(|segment| !segment.source.is_empty())
+            .collect();
+        if priority == Priority::Last {
+            segments.reverse();
+        }
+
+        segments
+            .into_iter()
+            .fold(IntervalMap::default(), |accepted, segment| {
+                let uncovered = accepted
+                    .covering(segment.source)
+                    .into_iter()
+                    .filter(|(_, covered_by)| covered_by.is_none())
+                    .map(|(piece, _)| Segment::new(piece, segment.offset))
+                    .collect::<Vec<_>>();
+
+                let mut segments = accepted.segments;
+                segments.extend(uncovered);
+                segments.sort_by_key(|segment| segment.source.start);
+                IntervalMap { segments }
+            })
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn map(&self, value: i64) -> i64 {
+        let i = self
+            .segments
+            .partition_point(|segment| segment.source.end < value);
+
+        match self.segments.get(i) {
+            Some(segment) if segment.source.start <= value => value + segment.offset,
+            _ => value,
+        }
+    }
+
+    pub fn map_range(&self, range: Range) -> Vec<Range> {
+        self.pieces(range)
+            .into_iter()
+            .map(|(piece, offset)| piece.shift(offset))
+            .collect()
+    }
+
+    // Splits `range` into consecutive pieces, each paired with the offset it is mapped by.
+    // Pieces that fall between segments map to themselves with an offset of 0.
+    pub fn pieces(&self, range: Range) -> Vec<(Range, i64)> {
+        self.covering(range)
+            .into_iter()
+            .map(|(piece, segment)| (piece, segment.map_or(0, |segment| segment.offset)))
+            .collect()
+    }
+
+    // Every source range whose values map into `range`, paired with the offset it is mapped by.
+    pub fn preimage_pieces(&self, range: Range) -> Vec<(Range, i64)> {
+        let moved = self.segments.iter().filter_map(|segment| {
+            let destination = segment.destination().intersect(&range)?;
+            Some((destination.shift(-segment.offset), segment.offset))
+        });
+        let unmoved = self
+            .covering(range)
+            .into_iter()
+            .filter(|(_, covered_by)| covered_by.is_none())
+            .map(|(piece, _)| (piece, 0));
+
+        let mut pieces: Vec<(Range, i64)> = moved.chain(unmoved).collect();
+        pieces.sort_by_key(|(piece, _)| piece.start);
+        pieces
+    }
+
+    pub fn preimage(&self, range: Range) -> Vec<Range> {
+        merge_ranges(
+            self.preimage_pieces(range)
+                .into_iter()
+                .map(|(piece, _)| piece)
+                .collect(),
+        )
+    }
+
+    // Applies `self` and then `next`, as a single map.
+    pub fn compose(&self, next: &IntervalMap) -> IntervalMap {
+        let moved_by_self = self.segments.iter().flat_map(|segment| {
+            next.pieces(segment.destination())
+                .into_iter()
+                .map(|(piece, offset)| {
+                    Segment::new(piece.shift(-segment.offset), segment.offset + offset)
+                })
+        });
+        let moved_by_next = next.segments.iter().flat_map(|segment| {
+            self.covering(segment.source)
+                .into_iter()
+                .filter(|(_, covered_by)| covered_by.is_none())
+                .map(|(piece, _)| Segment::new(piece, segment.offset))
+        });
+
+        let mut segments: Vec<Segment> = moved_by_self
+            .chain(moved_by_next)
+            .filter(|segment| segment.offset != 0)
+            .collect();
+        segments.sort_by_key(|segment| segment.source.start);
+
+        let segments = segments
+            .into_iter()
+            .fold(vec![], |mut merged: Vec<Segment>, segment| {
+                match merged.last_mut() {
+                    Some(last)
+                        if last.offset == segment.offset
+                            && last.source.end + 1 == segment.source.start =>
+                    {
+                        last.source.end = segment.source.end;
+                    }
+                    _ => merged.push(segment),
+                }
+                merged
+            });
+
+        IntervalMap { segments }
+    }
+
+    pub fn compose_all<'a>(maps: impl IntoIterator<Item = &'a IntervalMap>) -> IntervalMap {
+        maps.into_iter()
+            .fold(IntervalMap::default(), |composed, map| {
+                composed.compose(map)
+            })
+    }
+
+    // Every value at which the mapping offset can change.
+    pub fn breakpoints(&self) -> Vec<i64> {
+        let mut breakpoints: Vec<i64> = self
+            .segments
+            .iter()
+            .flat_map(|segment| [segment.source.start, segment.source.end + 1])
+            .collect();
+        breakpoints.dedup();
+        breakpoints
+    }
+
+    fn covering(&self, range: Range) -> Vec<(Range, Option<&Segment>)> {
+        let mut pieces = vec![];
+        let mut cursor = range.start;
+        let first = self
+            .segments
+            .partition_point(|segment| segment.source.end < range.start);
+
+        for segment in &self.segments[first..] {
+            if segment.source.start > range.end {
+                break;
+            }
+            if cursor < segment.source.start {
+                pieces.push((Range::new(cursor, segment.source.start - 1), None));
+            }
+
+            let overlap = Range::new(
+                cursor.max(segment.source.start),
+                range.end.min(segment.source.end),
+            );
+            pieces.push((overlap, Some(segment)));
+            cursor = overlap.end + 1;
+        }
+
+        if cursor <= range.end {
+            pieces.push((Range::new(cursor, range.end), None));
+        }
+
+        pieces
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    First,
+    Last,
+}
+
+pub fn merge_ranges(mut ranges: Vec<Range>) -> Vec<Range> {
+    ranges.sort();
+    ranges
+        .into_iter()
+        .fold(vec![], |mut merged: Vec<Range>, range| {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end + 1 => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+            merged
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed_to_soil() -> IntervalMap {
+        IntervalMap::new(["50 98 2", "52 50 48"].map(|line| Segment::try_from(line).unwrap()))
+            .unwrap()
+    }
+
+    #[test]
+    fn maps_values() {
+        let map = seed_to_soil();
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(14), 14);
+        assert_eq!(map.map(98), 50);
+        assert_eq!(map.map(99), 51);
+        assert_eq!(map.map(100), 100);
+        assert_eq!(map.map(49), 49);
+    }
+
+    #[test]
+    fn maps_ranges() {
+        let map = seed_to_soil();
+        assert_eq!(map.map_range(Range::new(55, 67)), vec![Range::new(57, 69)]);
+        assert_eq!(
+            map.map_range(Range::new(40, 120)),
+            vec![
+                Range::new(40, 49),
+                Range::new(52, 99),
+                Range::new(50, 51),
+                Range::new(100, 120),
+            ]
+        );
+        assert_eq!(map.map_range(Range::new(0, 10)), vec![Range::new(0, 10)]);
+    }
+
+    #[test]
+    fn preimage_of_ranges() {
+        let map = seed_to_soil();
+        assert_eq!(map.preimage(Range::new(50, 51)), vec![Range::new(98, 99)]);
+        assert_eq!(map.preimage(Range::new(52, 53)), vec![Range::new(50, 51)]);
+        assert_eq!(
+            map.preimage(Range::new(100, 110)),
+            vec![Range::new(100, 110)]
+        );
+        assert_eq!(map.preimage(Range::new(0, 120)), vec![Range::new(0, 120)]);
+
+        for target in 0..120 {
+            for range in map.preimage(Range::new(target, target)) {
+                for value in range.start..=range.end {
+                    assert_eq!(map.map(value), target);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn composes_maps() {
+        let seed_to_soil = seed_to_soil();
+        let soil_to_fertilizer = IntervalMap::new(
+            ["0 15 37", "37 52 2", "39 0 15"].map(|line| Segment::try_from(line).unwrap()),
+        )
+        .unwrap();
+        let composed = seed_to_soil.compose(&soil_to_fertilizer);
+
+        for seed in -5..120 {
+            assert_eq!(
+                composed.map(seed),
+                soil_to_fertilizer.map(seed_to_soil.map(seed)),
+                "seed {seed}"
+            );
+        }
+        assert_eq!(composed.breakpoints(), vec![0, 15, 50, 52, 98, 100]);
+    }
+
+    #[test]
+    fn compose_with_identity() {
+        let map = seed_to_soil();
+        assert_eq!(IntervalMap::default().compose(&map), map);
+        assert_eq!(map.compose(&IntervalMap::default()), map);
+    }
+
+    #[test]
+    fn normalizes_overlapping_segments() {
+        let segments =
+            ["10 0 10", "100 5 10", "0 20 0"].map(|line| Segment::try_from(line).unwrap());
+
+        let first = IntervalMap::normalized(segments, Priority::First);
+        assert_eq!(
+            first.segments(),
+            &[
+                Segment::new(Range::new(0, 9), 10),
+                Segment::new(Range::new(10, 14), 95),
+            ]
+        );
+
+        let last = IntervalMap::normalized(segments, Priority::Last);
+        assert_eq!(
+            last.segments(),
+            &[
+                Segment::new(Range::new(0, 4), 10),
+                Segment::new(Range::new(5, 14), 95),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_overlapping_and_empty_segments() {
+        let overlapping = ["50 98 2", "52 50 49"].map(|line| Segment::try_from(line).unwrap());
+        assert!(IntervalMap::new(overlapping).is_err());
+
+        let empty = ["50 98 0"].map(|line| Segment::try_from(line).unwrap());
+        assert!(IntervalMap::new(empty).is_err());
+
+        assert!(Segment::try_from("50 98").is_err());
+        assert!(Segment::try_from("50 x 2").is_err());
+    }
+}
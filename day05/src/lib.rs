@@ -1,3 +1,5 @@
+pub mod almanac;
+pub mod interval_map;
 mod part1;
 mod part2;
 
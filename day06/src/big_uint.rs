@@ -0,0 +1,232 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+// Arbitrary precision unsigned integer, stored as little endian base 2^32 limbs without
+// trailing zero limbs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn from_u64(n: u64) -> Self {
+        Self::from_limbs(vec![n as u32, (n >> 32) as u32])
+    }
+
+    pub fn from_u128(n: u128) -> Self {
+        Self::from_limbs((0..4).map(|i| (n >> (32 * i)) as u32).collect())
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(
+            self.limbs
+                .iter()
+                .rev()
+                .fold(0, |n, &limb| (n << 32) | limb as u128),
+        )
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Self { limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn bits(&self) -> u32 {
+        match self.limbs.last() {
+            Some(last) => self.limbs.len() as u32 * 32 - last.leading_zeros(),
+            None => 0,
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0u64;
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = *self.limbs.get(i).unwrap_or(&0) as u64
+                + *other.limbs.get(i).unwrap_or(&0) as u64
+                + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        Self::from_limbs(limbs)
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        assert!(*self >= *other, "subtraction should not underflow");
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for (i, &limb) in self.limbs.iter().enumerate() {
+            let mut diff = limb as i64 - *other.limbs.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = (diff < 0) as i64;
+            if diff < 0 {
+                diff += 1 << 32;
+            }
+            limbs.push(diff as u32);
+        }
+        Self::from_limbs(limbs)
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        Self::from_limbs(limbs)
+    }
+
+    pub fn shl(&self, bits: u32) -> Self {
+        if self.is_zero() {
+            return Self::default();
+        }
+        let (limb_shift, bit_shift) = ((bits / 32) as usize, bits % 32);
+        let mut limbs = vec![0u32; limb_shift];
+        let mut carry = 0u32;
+        for &limb in self.limbs.iter() {
+            limbs.push((limb << bit_shift) | carry);
+            carry = if bit_shift == 0 {
+                0
+            } else {
+                limb >> (32 - bit_shift)
+            };
+        }
+        limbs.push(carry);
+        Self::from_limbs(limbs)
+    }
+
+    pub fn shr(&self, bits: u32) -> Self {
+        let (limb_shift, bit_shift) = ((bits / 32) as usize, bits % 32);
+        let Some(limbs) = self.limbs.get(limb_shift..) else {
+            return Self::default();
+        };
+        let limbs = limbs
+            .iter()
+            .enumerate()
+            .map(|(i, &limb)| {
+                let high = match (bit_shift, limbs.get(i + 1)) {
+                    (0, _) | (_, None) => 0,
+                    (_, Some(&next)) => next << (32 - bit_shift),
+                };
+                (limb >> bit_shift) | high
+            })
+            .collect();
+        Self::from_limbs(limbs)
+    }
+
+    fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        let mut limbs = vec![0u32; self.limbs.len()];
+        let mut rem = 0u64;
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let current = (rem << 32) | limb as u64;
+            limbs[i] = (current / divisor as u64) as u32;
+            rem = current % divisor as u64;
+        }
+        (Self::from_limbs(limbs), rem as u32)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("\"\" is not a valid number.".to_string());
+        }
+
+        let ten = BigUint::from_u64(10);
+        s.chars().try_fold(BigUint::default(), |n, c| {
+            let digit = c
+                .to_digit(10)
+                .ok_or_else(|| format!("\"{s}\" is not a valid number."))?;
+            Ok(n.mul(&ten).add(&BigUint::from_u64(digit as u64)))
+        })
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let mut digits = vec![];
+        let mut n = self.clone();
+        while !n.is_zero() {
+            let (quotient, rem) = n.div_rem_small(10);
+            digits.push(char::from_digit(rem, 10).unwrap());
+            n = quotient;
+        }
+        write!(f, "{}", digits.iter().rev().collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn round_trips_decimal() {
+        for s in ["0", "7", "4294967296", "123456789012345678901234567890123"] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert!("12a".parse::<BigUint>().is_err());
+    }
+
+    #[test]
+    fn round_trips_u128() {
+        for n in [0, 1, u64::MAX as u128 + 1, u128::MAX] {
+            assert_eq!(BigUint::from_u128(n).to_u128(), Some(n));
+        }
+        assert_eq!(BigUint::from_u128(u128::MAX).add(&big("1")).to_u128(), None);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = big("123456789012345678901234567890");
+        let b = big("987654321098765432109876543210");
+        assert_eq!(a.add(&b), big("1111111110111111111011111111100"));
+        assert_eq!(b.sub(&a), big("864197532086419753208641975320"));
+        assert_eq!(
+            a.mul(&b),
+            big("121932631137021795226185032733622923332237463801111263526900")
+        );
+        assert_eq!(a.shl(70).shr(70), a);
+        assert_eq!(a.shr(1), big("61728394506172839450617283945"));
+        assert_eq!(BigUint::from_u64(1).shl(64), big("18446744073709551616"));
+        assert!(a < b);
+        assert_eq!(a.sub(&a), BigUint::default());
+    }
+}
@@ -1,5 +1,9 @@
+pub mod big_uint;
 mod part1;
 mod part2;
+pub mod physics;
+pub mod race;
+pub mod sheet;
 
 pub use part1::part1;
 pub use part2::part2;
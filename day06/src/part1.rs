@@ -1,27 +1,18 @@
-pub fn part1(input: &str) -> i32 {
-    let mut lines = input.lines();
-
-    let time = lines
-        .next()
-        .unwrap()
-        .split(' ')
-        .filter_map(|s| s.parse::<i32>().ok());
+use crate::sheet::RaceSheet;
 
-    let distance = lines
-        .next()
-        .unwrap()
-        .split(' ')
-        .filter_map(|s| s.parse::<i32>().ok());
+pub fn part1(input: &str) -> i32 {
+    let sheet = RaceSheet::try_from(input).expect("race sheet should be parsable");
 
-    time.zip(distance).fold(1, |acc, (time, distance)| {
-        acc * (0..=time)
-            .filter(|ms_button_held| {
-                let time_to_travel = time - ms_button_held;
-                let distance_traveled = ms_button_held * time_to_travel;
-                distance_traveled > distance
-            })
-            .count() as i32
-    })
+    sheet
+        .races()
+        .expect("races should fit in u64")
+        .iter()
+        .try_fold(1i32, |product, race| {
+            i32::try_from(race.count_ways())
+                .ok()
+                .and_then(|ways| product.checked_mul(ways))
+        })
+        .expect("product of ways should fit in an i32")
 }
 
 #[cfg(test)]
@@ -43,4 +34,10 @@ mod tests {
         let expected = 800280;
         assert_eq!(output, expected);
     }
+
+    #[test]
+    #[should_panic(expected = "product of ways should fit in an i32")]
+    fn part1_product_beyond_i32() {
+        part1("Time:      100000 100000\nDistance:  1 1");
+    }
 }
@@ -0,0 +1,174 @@
+use crate::race::winning_holds;
+
+// `charge_rate` is the speed gained per ms the button is held, capped at `max_speed`. The boat
+// starts moving `startup_delay` ms after the button is released and then loses `drag` of its
+// speed for every ms it has been moving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Physics {
+    pub charge_rate: u64,
+    pub max_speed: Option<u64>,
+    pub startup_delay: u64,
+    pub drag: u64,
+}
+
+impl Default for Physics {
+    fn default() -> Self {
+        Self {
+            charge_rate: 1,
+            max_speed: None,
+            startup_delay: 0,
+            drag: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u64,
+    pub record: u64,
+    pub physics: Physics,
+}
+
+impl Race {
+    pub fn new(time: u64, record: u64) -> Self {
+        Self {
+            time,
+            record,
+            physics: Physics::default(),
+        }
+    }
+
+    pub fn with_physics(self, physics: Physics) -> Self {
+        Self { physics, ..self }
+    }
+
+    pub fn distance(&self, hold: u64) -> u128 {
+        let Physics {
+            charge_rate,
+            max_speed,
+            startup_delay,
+            drag,
+        } = self.physics;
+
+        let speed = hold as u128 * charge_rate as u128;
+        let speed = max_speed.map_or(speed, |max_speed| speed.min(max_speed as u128));
+        let moving_time = self.time.saturating_sub(hold).saturating_sub(startup_delay) as u128;
+
+        if drag == 0 {
+            return speed * moving_time;
+        }
+
+        let drag = drag as u128;
+        let moving_steps = moving_time.min(speed.div_ceil(drag));
+        moving_steps * speed - drag * moving_steps * moving_steps.saturating_sub(1) / 2
+    }
+
+    pub fn count_ways(&self) -> u64 {
+        if self.physics.drag != 0 || self.physics.charge_rate == 0 {
+            return self.count_ways_by_search();
+        }
+
+        let moving_time = match self.time.checked_sub(self.physics.startup_delay) {
+            Some(moving_time) => moving_time as u128,
+            None => return 0,
+        };
+        let charge_rate = self.physics.charge_rate as u128;
+        let record = self.record as u128;
+
+        // Uncapped, `charge_rate * h * (moving_time - h) > record` is the same race as
+        // `h * (moving_time - h) > record / charge_rate`.
+        let uncapped = winning_holds(&moving_time, &(record / charge_rate));
+        let Some(max_speed) = self.physics.max_speed else {
+            return uncapped.map_or(0, |(lowest, highest)| (highest - lowest + 1) as u64);
+        };
+        if max_speed == 0 {
+            return 0;
+        }
+
+        // Holding for at least `capped_from` ms reaches the speed cap, after which the distance
+        // is `max_speed * (moving_time - h)`.
+        let max_speed = max_speed as u128;
+        let capped_from = max_speed.div_ceil(charge_rate);
+
+        let below_cap = uncapped.map_or(0, |(lowest, highest)| {
+            let highest = highest.min(capped_from.saturating_sub(1));
+            if lowest > highest {
+                0
+            } else {
+                highest - lowest + 1
+            }
+        });
+        let at_cap = match moving_time.checked_sub(record / max_speed + 1) {
+            Some(highest) if highest >= capped_from => highest - capped_from + 1,
+            _ => 0,
+        };
+
+        (below_cap + at_cap) as u64
+    }
+
+    pub fn count_ways_by_search(&self) -> u64 {
+        (0..=self.time)
+            .filter(|&hold| self.distance(hold) > self.record as u128)
+            .count() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_physics_example() {
+        let ways: Vec<u64> = [(7, 9), (15, 40), (30, 200)]
+            .into_iter()
+            .map(|(time, record)| Race::new(time, record).count_ways())
+            .collect();
+        assert_eq!(ways, vec![4, 8, 9]);
+    }
+
+    #[test]
+    fn closed_forms_match_search() {
+        for time in 0..40 {
+            for record in (0..120).step_by(7) {
+                for charge_rate in 1..4 {
+                    for max_speed in [None, Some(0), Some(1), Some(5), Some(13)] {
+                        for startup_delay in [0, 1, 6] {
+                            let race = Race::new(time, record).with_physics(Physics {
+                                charge_rate,
+                                max_speed,
+                                startup_delay,
+                                drag: 0,
+                            });
+                            assert_eq!(race.count_ways(), race.count_ways_by_search(), "{race:?}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn drag_slows_the_boat() {
+        let race = Race::new(10, 0).with_physics(Physics {
+            drag: 2,
+            ..Physics::default()
+        });
+        // speed 5 for 5 ms: 5 + 3 + 1.
+        assert_eq!(race.distance(5), 9);
+        // speed 8 for 2 ms: 8 + 6.
+        assert_eq!(race.distance(8), 14);
+        assert_eq!(race.distance(0), 0);
+
+        let race = Race { record: 12, ..race };
+        assert_eq!(race.count_ways(), 2);
+    }
+
+    #[test]
+    fn startup_delay_longer_than_race() {
+        let race = Race::new(5, 0).with_physics(Physics {
+            startup_delay: 10,
+            ..Physics::default()
+        });
+        assert_eq!(race.count_ways(), 0);
+    }
+}
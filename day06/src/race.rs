@@ -0,0 +1,252 @@
+use crate::big_uint::BigUint;
+
+pub trait Natural: Clone + Ord {
+    // A type that can hold the square of any value, so the closed form never overflows.
+    type Wide: Natural;
+
+    fn widen(&self) -> Self::Wide;
+    fn narrow(wide: &Self::Wide) -> Self;
+    fn from_u64(n: u64) -> Self;
+    fn add(&self, other: &Self) -> Self;
+    fn sub(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+    fn shl(&self, bits: u32) -> Self;
+    fn shr(&self, bits: u32) -> Self;
+    fn bits(&self) -> u32;
+}
+
+macro_rules! impl_natural {
+    ($($t:ty => $wide:ty, $widen:expr, $narrow:expr);*) => {$(
+        impl Natural for $t {
+            type Wide = $wide;
+
+            fn widen(&self) -> Self::Wide {
+                $widen(*self)
+            }
+
+            fn narrow(wide: &Self::Wide) -> Self {
+                $narrow(wide).expect("narrowed value should fit its original type")
+            }
+
+            fn from_u64(n: u64) -> Self {
+                n as $t
+            }
+
+            fn add(&self, other: &Self) -> Self {
+                self.checked_add(*other).expect("race numbers should not overflow")
+            }
+
+            fn sub(&self, other: &Self) -> Self {
+                self - other
+            }
+
+            fn mul(&self, other: &Self) -> Self {
+                self.checked_mul(*other).expect("race numbers should not overflow")
+            }
+
+            fn shl(&self, bits: u32) -> Self {
+                assert!(self.leading_zeros() >= bits, "race numbers should not overflow");
+                self << bits
+            }
+
+            fn shr(&self, bits: u32) -> Self {
+                self >> bits
+            }
+
+            fn bits(&self) -> u32 {
+                <$t>::BITS - self.leading_zeros()
+            }
+        }
+    )*};
+}
+
+impl_natural!(
+    u64 => u128, u128::from, |wide: &u128| u64::try_from(*wide).ok();
+    u128 => BigUint, BigUint::from_u128, BigUint::to_u128
+);
+
+impl Natural for BigUint {
+    type Wide = BigUint;
+
+    fn widen(&self) -> Self::Wide {
+        self.clone()
+    }
+
+    fn narrow(wide: &Self::Wide) -> Self {
+        wide.clone()
+    }
+
+    fn from_u64(n: u64) -> Self {
+        BigUint::from_u64(n)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        BigUint::add(self, other)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        BigUint::sub(self, other)
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        BigUint::mul(self, other)
+    }
+
+    fn shl(&self, bits: u32) -> Self {
+        BigUint::shl(self, bits)
+    }
+
+    fn shr(&self, bits: u32) -> Self {
+        BigUint::shr(self, bits)
+    }
+
+    fn bits(&self) -> u32 {
+        BigUint::bits(self)
+    }
+}
+
+// Digit by digit square root, which only needs shifts, additions and comparisons.
+pub fn isqrt<N: Natural>(n: &N) -> N {
+    let zero = N::from_u64(0);
+    let mut rem = n.clone();
+    let mut root = zero.clone();
+    let mut bit = match n.bits() {
+        0 => return zero,
+        bits => N::from_u64(1).shl((bits - 1) & !1),
+    };
+
+    while bit > zero {
+        let candidate = root.add(&bit);
+        if rem >= candidate {
+            rem = rem.sub(&candidate);
+            root = root.shr(1).add(&bit);
+        } else {
+            root = root.shr(1);
+        }
+        bit = bit.shr(2);
+    }
+
+    root
+}
+
+// Counts the hold times `h` in `0..=time` with `h * (time - h) > distance`.
+pub fn count_ways<N: Natural>(time: &N, distance: &N) -> N {
+    match winning_holds(time, distance) {
+        Some((lowest, highest)) => highest.sub(&lowest).add(&N::from_u64(1)),
+        None => N::from_u64(0),
+    }
+}
+
+// The lowest and highest winning hold times. They lie strictly between the roots
+// `(time ± sqrt(time² - 4 * distance)) / 2` and are symmetric around `time / 2`, so only the
+// lowest one has to be found. The search runs in `N::Wide`, where `time²` cannot overflow.
+pub fn winning_holds<N: Natural>(time: &N, distance: &N) -> Option<(N, N)> {
+    let (lowest, highest) = wide_winning_holds(&time.widen(), &distance.widen())?;
+    Some((N::narrow(&lowest), N::narrow(&highest)))
+}
+
+fn wide_winning_holds<N: Natural>(time: &N, distance: &N) -> Option<(N, N)> {
+    let zero = N::from_u64(0);
+    let one = N::from_u64(1);
+    let beats = |hold: &N| hold.mul(&time.sub(hold)) > *distance;
+
+    let time_squared = time.mul(time);
+    let four_distance = distance.shl(2);
+    if time_squared <= four_distance {
+        return None;
+    }
+
+    let root = isqrt(&time_squared.sub(&four_distance));
+    let mut hold = time.sub(&root).shr(1);
+    while hold > zero && beats(&hold.sub(&one)) {
+        hold = hold.sub(&one);
+    }
+    while !beats(&hold) {
+        hold = hold.add(&one);
+        if hold > time.shr(1) {
+            return None;
+        }
+    }
+
+    let highest = time.sub(&hold);
+    Some((hold, highest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(time: u64, distance: u64) -> u64 {
+        (0..=time)
+            .filter(|hold| hold * (time - hold) > distance)
+            .count() as u64
+    }
+
+    #[test]
+    fn isqrt_exact() {
+        for n in 0..10_000u64 {
+            let root = isqrt(&n);
+            assert!(root * root <= n && (root + 1) * (root + 1) > n, "{n}");
+        }
+        assert_eq!(isqrt(&u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for time in 0..60 {
+            for distance in 0..=(time * time / 4 + 2) {
+                assert_eq!(
+                    count_ways(&time, &distance),
+                    brute_force(time, distance),
+                    "time {time} distance {distance}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn u64_beyond_squarable_times() {
+        let time: u64 = 5_000_000_000;
+        assert_eq!(count_ways(&time, &1), time - 1);
+        assert_eq!(count_ways(&u64::MAX, &1), u64::MAX - 1);
+        assert_eq!(count_ways(&u64::MAX, &u64::MAX), u64::MAX - 3);
+    }
+
+    #[test]
+    fn u128_beyond_squarable_times() {
+        assert_eq!(count_ways(&u128::MAX, &1), u128::MAX - 1);
+    }
+
+    #[test]
+    fn u128_near_float_limits() {
+        // 2^53 + 1 cannot be represented as an f64.
+        let time: u128 = (1 << 53) + 1;
+        let hold: u128 = 12_345;
+        let distance = hold * (time - hold);
+        assert_eq!(count_ways(&time, &distance), time - 2 * hold - 1);
+        assert_eq!(count_ways(&time, &(distance - 1)), time - 2 * hold + 1);
+    }
+
+    #[test]
+    fn big_uint_with_30_plus_digits() {
+        let time: BigUint = "987654321098765432109876543210987".parse().unwrap();
+        let hold: BigUint = "123456789012345678901234567890".parse().unwrap();
+        let distance = hold.mul(&time.sub(&hold));
+        let expected = time.sub(&hold.shl(1)).sub(&BigUint::from_u64(1));
+        assert_eq!(count_ways(&time, &distance), expected);
+        assert_eq!(
+            count_ways(&time, &distance.sub(&BigUint::from_u64(1))),
+            expected.add(&BigUint::from_u64(2))
+        );
+    }
+
+    #[test]
+    fn big_uint_matches_u64() {
+        for (time, distance) in [(7, 9), (15, 40), (30, 200), (71530, 940200)] {
+            assert_eq!(
+                count_ways(&BigUint::from_u64(time), &BigUint::from_u64(distance)),
+                BigUint::from_u64(count_ways(&time, &distance))
+            );
+        }
+    }
+}
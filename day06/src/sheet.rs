@@ -0,0 +1,167 @@
+use crate::physics::{Physics, Race};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceSheet {
+    rows: Vec<Row>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub label: String,
+    pub columns: Vec<String>,
+}
+
+impl RaceSheet {
+    pub fn row(&self, label: &str) -> Result<&Row, String> {
+        self.rows
+            .iter()
+            .find(|row| row.label == label)
+            .ok_or_else(|| format!("race sheet has no \"{label}\" row."))
+    }
+
+    pub fn values<N: FromStr>(&self, label: &str) -> Result<Vec<N>, String> {
+        self.row(label)?
+            .columns
+            .iter()
+            .map(|column| {
+                column
+                    .parse()
+                    .map_err(|_| format!("\"{column}\" in \"{label}\" is out of range."))
+            })
+            .collect()
+    }
+
+    // Reads the row as a single number, ignoring the spaces between its columns.
+    pub fn kerned<N: FromStr>(&self, label: &str) -> Result<N, String> {
+        let digits = self.row(label)?.columns.concat();
+        digits
+            .parse()
+            .map_err(|_| format!("\"{digits}\" in \"{label}\" is out of range."))
+    }
+
+    // Every column is a separate race. An optional `Handicap` row delays each boat's start.
+    pub fn races(&self) -> Result<Vec<Race>, String> {
+        let times = self.values::<u64>("Time")?;
+        let records = self.values::<u64>("Distance")?;
+        let handicaps = match self.row("Handicap") {
+            Ok(_) => self.values::<u64>("Handicap")?,
+            Err(_) => vec![0; times.len()],
+        };
+
+        Ok(times
+            .into_iter()
+            .zip(records)
+            .zip(handicaps)
+            .map(|((time, record), startup_delay)| {
+                Race::new(time, record).with_physics(Physics {
+                    startup_delay,
+                    ..Physics::default()
+                })
+            })
+            .collect())
+    }
+
+    pub fn kerned_race(&self) -> Result<Race, String> {
+        Ok(Race::new(self.kerned("Time")?, self.kerned("Distance")?))
+    }
+}
+
+impl TryFrom<&str> for RaceSheet {
+    type Error = String;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let rows = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (label, columns) = line
+                    .split_once(':')
+                    .ok_or_else(|| format!("\"{line}\" is not a valid race sheet row."))?;
+                let columns = columns
+                    .split_whitespace()
+                    .map(|column| match column.chars().all(|c| c.is_ascii_digit()) {
+                        true => Ok(column.to_string()),
+                        false => Err(format!("\"{column}\" in \"{label}\" is not a number.")),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(Row {
+                    label: label.trim().to_string(),
+                    columns,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let sheet = Self { rows };
+        let time_columns = sheet.row("Time")?.columns.len();
+        sheet.row("Distance")?;
+
+        if let Some(row) = sheet
+            .rows
+            .iter()
+            .find(|row| row.columns.len() != time_columns)
+        {
+            return Err(format!(
+                "\"{}\" has {} columns but \"Time\" has {time_columns}.",
+                row.label,
+                row.columns.len()
+            ));
+        }
+
+        Ok(sheet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{big_uint::BigUint, race::count_ways};
+
+    #[test]
+    fn both_interpretations_example() {
+        let sheet = RaceSheet::try_from(include_str!("../example.txt")).unwrap();
+        let races = sheet.races().unwrap();
+        assert_eq!(
+            races,
+            vec![Race::new(7, 9), Race::new(15, 40), Race::new(30, 200)]
+        );
+        assert_eq!(sheet.kerned_race().unwrap(), Race::new(71530, 940200));
+    }
+
+    #[test]
+    fn handicap_row() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200\nHandicap:  0   1    1";
+        let sheet = RaceSheet::try_from(input).unwrap();
+        let ways: Vec<u64> = sheet
+            .races()
+            .unwrap()
+            .iter()
+            .map(Race::count_ways)
+            .collect();
+        assert_eq!(ways, vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn kerned_big_numbers() {
+        let input = "Time:      98765432109876 543210987654 321098765\nDistance:  12193263113702179 4183661029901219315930 20879193607986";
+        let sheet = RaceSheet::try_from(input).unwrap();
+        assert!(sheet.kerned_race().is_err());
+
+        let time: BigUint = sheet.kerned("Time").unwrap();
+        let distance: BigUint = sheet.kerned("Distance").unwrap();
+        assert_eq!(time.to_string(), "98765432109876543210987654321098765");
+        assert_eq!(
+            count_ways(&time, &distance).to_string(),
+            "98765432109876542964074076296407408"
+        );
+    }
+
+    #[test]
+    fn reports_mismatched_columns() {
+        let err = RaceSheet::try_from("Time: 1 2 3\nDistance: 4 5").unwrap_err();
+        assert_eq!(err, "\"Distance\" has 2 columns but \"Time\" has 3.");
+        assert!(RaceSheet::try_from("Time: 1 2").is_err());
+        assert!(RaceSheet::try_from("Time: 1 x\nDistance: 4 5").is_err());
+    }
+}
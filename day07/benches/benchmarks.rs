@@ -1,3 +1,7 @@
+use std::{fmt::Write, sync::OnceLock};
+
+use day07::rules::{total_winnings, Ruleset, TiePolicy};
+
 fn main() {
     divan::main();
 }
@@ -13,3 +17,46 @@ fn part2() {
     let input = include_str!("../input.txt");
     day07::part2(divan::black_box(input));
 }
+
+#[divan::bench]
+fn standard_generated() -> i64 {
+    total_winnings(
+        divan::black_box(generated_input()),
+        &Ruleset::standard(),
+        TiePolicy::InputOrder,
+    )
+}
+
+#[divan::bench]
+fn jokers_generated() -> i64 {
+    total_winnings(
+        divan::black_box(generated_input()),
+        &Ruleset::jokers(),
+        TiePolicy::InputOrder,
+    )
+}
+
+const GENERATED_HANDS: u32 = 1_000_000;
+
+fn generated_input() -> &'static str {
+    static INPUT: OnceLock<String> = OnceLock::new();
+    INPUT.get_or_init(|| {
+        let mut seed: u64 = 0x2023_1207;
+        let mut next_num = |bound: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % bound
+        };
+
+        let cards = b"23456789TJQKA";
+        let mut input = String::new();
+        for _ in 0..GENERATED_HANDS {
+            for _ in 0..5 {
+                input.push(cards[next_num(cards.len() as u64) as usize] as char);
+            }
+            writeln!(input, " {}", next_num(1000) + 1).unwrap();
+        }
+        input
+    })
+}
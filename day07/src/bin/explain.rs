@@ -0,0 +1,18 @@
+use day07::{
+    rules::{Ruleset, TiePolicy},
+    substitution::{explain, format_explanations},
+};
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+fn main() {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    let input = include_str!("../../input.txt");
+    let ruleset = Ruleset::jokers();
+    let explanations = explain(input, &ruleset, TiePolicy::InputOrder);
+    print!("{}", format_explanations(&explanations, &ruleset));
+}
@@ -1,5 +1,7 @@
 mod part1;
 mod part2;
+pub mod rules;
+pub mod substitution;
 
 pub use part1::part1;
 pub use part2::part2;
@@ -0,0 +1,626 @@
+pub fn total_winnings(input: &str, ruleset: &Ruleset, tie_policy: TiePolicy) -> i64 {
+    rank_hands(parse_hands(input, ruleset), tie_policy)
+        .into_iter()
+        .try_fold(0i64, |total, ranked| {
+            (ranked.rank as i64)
+                .checked_mul(ranked.hand.bid as i64)
+                .and_then(|winnings| total.checked_add(winnings))
+        })
+        .expect("total winnings should fit in an i64")
+}
+
+pub fn parse_hands(input: &str, ruleset: &Ruleset) -> Vec<Hand> {
+    input
+        .lines()
+        .map(|line| {
+            let (hand, bid) = line.split_once(' ').unwrap();
+            let mut cards = Vec::with_capacity(ruleset.hand_size());
+            for c in hand.chars() {
+                cards.push(CardType::try_from(c).expect("Card to be valid CardType"));
+            }
+            assert_eq!(
+                cards.len(),
+                ruleset.hand_size(),
+                "Hand to have {} cards",
+                ruleset.hand_size()
+            );
+            let bid = bid.parse::<i32>().expect("Bid to be a valid integer");
+            Hand::new(cards, bid, ruleset)
+        })
+        .collect()
+}
+
+// Ranks every hand from weakest (rank 1) to strongest. Hands of equal strength either keep
+// their input order or all share the lowest rank among them, skipping the ranks they cover.
+pub fn rank_hands(hands: Vec<Hand>, tie_policy: TiePolicy) -> Vec<RankedHand> {
+    // Sorting small (key, index) pairs and moving each hand once is much faster than sorting the
+    // hands themselves.
+    let mut order: Vec<(u64, usize)> = hands.iter().map(|hand| hand.key).zip(0..).collect();
+    order.sort_unstable();
+    let mut hands: Vec<Option<Hand>> = hands.into_iter().map(Some).collect();
+
+    let mut ranked: Vec<RankedHand> = Vec::with_capacity(hands.len());
+    for (i, &(_, index)) in order.iter().enumerate() {
+        let hand = hands[index].take().expect("each hand to be ranked once");
+        let rank = match (tie_policy, ranked.last()) {
+            (TiePolicy::SharedRank, Some(prev)) if prev.hand.key == hand.key => prev.rank,
+            _ => i + 1,
+        };
+        ranked.push(RankedHand { rank, hand });
+    }
+    ranked
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TiePolicy {
+    InputOrder,
+    SharedRank,
+}
+
+#[derive(Debug)]
+pub struct RankedHand {
+    pub rank: usize,
+    pub hand: Hand,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ruleset {
+    ranking: Vec<CardType>,
+    naturals: Vec<CardType>,
+    strengths: [u8; 13],
+    wild: u16,
+    tie_break: TieBreak,
+    hand_size: usize,
+    categories: Vec<Category>,
+}
+
+impl Ruleset {
+    // Keys pack the category above four bits per card, so hands can have at most 14 cards and
+    // a ruleset at most 256 categories.
+    const MAX_HAND_SIZE: usize = 14;
+    const MAX_CATEGORIES: usize = 256;
+
+    // `ranking` lists every card from weakest to strongest.
+    pub fn new(ranking: Vec<CardType>, wild: Vec<CardType>, tie_break: TieBreak) -> Self {
+        let mut strengths = [u8::MAX; 13];
+        for (strength, &card) in ranking.iter().enumerate() {
+            strengths[card as usize] = strength as u8;
+        }
+        assert!(
+            strengths.iter().all(|&strength| strength != u8::MAX),
+            "every card should be ranked"
+        );
+
+        let wild = wild.iter().fold(0, |mask, &card| mask | 1 << card as usize);
+        let naturals = ranking
+            .iter()
+            .filter(|&&card| wild & (1 << card as usize) == 0)
+            .copied()
+            .collect();
+
+        Self {
+            ranking,
+            naturals,
+            strengths,
+            wild,
+            tie_break,
+            hand_size: 5,
+            categories: HandType::ALL.into_iter().map(Category::from).collect(),
+        }
+    }
+
+    pub fn standard() -> Self {
+        use CardType::*;
+        Self::new(
+            vec![
+                Two, Three, Four, Five, Six, Seven, Eight, Nine, T, J, Q, K, A,
+            ],
+            vec![],
+            TieBreak::LeftToRight,
+        )
+    }
+
+    pub fn jokers() -> Self {
+        use CardType::*;
+        Self::new(
+            vec![
+                J, Two, Three, Four, Five, Six, Seven, Eight, Nine, T, Q, K, A,
+            ],
+            vec![J],
+            TieBreak::LeftToRight,
+        )
+    }
+
+    pub fn with_hand_size(self, hand_size: usize) -> Self {
+        assert!(
+            (1..=Self::MAX_HAND_SIZE).contains(&hand_size),
+            "hands should have 1 to {} cards",
+            Self::MAX_HAND_SIZE
+        );
+        Self { hand_size, ..self }
+    }
+
+    // `categories` lists every hand category from weakest to strongest. A hand takes the
+    // strongest category it matches, and the weakest category also catches every hand that
+    // matches nothing else. `Counts` groups may be given in any order.
+    pub fn with_categories(self, mut categories: Vec<Category>) -> Self {
+        assert!(
+            (1..=Self::MAX_CATEGORIES).contains(&categories.len()),
+            "rulesets should have 1 to {} categories",
+            Self::MAX_CATEGORIES
+        );
+        for category in categories.iter_mut() {
+            match &mut category.pattern {
+                Pattern::Counts(groups) => groups.sort_unstable_by(|a, b| b.cmp(a)),
+                Pattern::Straight(length) => assert!(
+                    *length >= 1,
+                    "straight \"{}\" should need at least one card",
+                    category.name
+                ),
+            }
+        }
+        Self { categories, ..self }
+    }
+
+    pub fn ranking(&self) -> &[CardType] {
+        &self.ranking
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    pub fn strength(&self, card: CardType) -> usize {
+        self.strengths[card as usize] as usize
+    }
+
+    pub fn is_wild(&self, card: CardType) -> bool {
+        self.wild & (1 << card as usize) != 0
+    }
+
+    // The index of the hand's category in `categories`.
+    pub fn category(&self, cards: &[CardType]) -> usize {
+        let mut counts = [0u8; 13];
+        let mut wild_count = 0;
+        for &card in cards {
+            if self.is_wild(card) {
+                wild_count += 1;
+            } else {
+                counts[card as usize] += 1;
+            }
+        }
+        self.matching_category(&counts, wild_count)
+    }
+
+    // The category `cards` would have if none of them were wild.
+    pub(crate) fn natural_category(&self, cards: &[CardType]) -> usize {
+        let mut counts = [0u8; 13];
+        for &card in cards {
+            counts[card as usize] += 1;
+        }
+        self.matching_category(&counts, 0)
+    }
+
+    fn matching_category(&self, counts: &[u8; 13], wild_count: u8) -> usize {
+        let mut buffer = [0u8; Self::MAX_HAND_SIZE];
+        let mut groups_found = 0;
+        for &count in counts.iter().filter(|&&count| count > 0) {
+            buffer[groups_found] = count;
+            groups_found += 1;
+        }
+        let sorted_counts = &mut buffer[..groups_found];
+        sorted_counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        self.categories
+            .iter()
+            .rposition(|category| match &category.pattern {
+                Pattern::Counts(groups) => {
+                    let shortfall: usize = groups
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &group)| {
+                            group
+                                .saturating_sub(sorted_counts.get(i).copied().unwrap_or(0) as usize)
+                        })
+                        .sum();
+                    shortfall <= wild_count as usize
+                }
+                Pattern::Straight(length) => self.naturals.windows(*length).any(|window| {
+                    let missing = window
+                        .iter()
+                        .filter(|&&card| counts[card as usize] == 0)
+                        .count();
+                    missing <= wild_count as usize
+                }),
+            })
+            .unwrap_or(0)
+    }
+
+    // Packs the category and the tie-break strengths into one integer, four bits per card, so
+    // hands can be ranked by comparing keys.
+    pub fn key(&self, cards: &[CardType]) -> u64 {
+        self.key_with_category(cards, self.category(cards))
+    }
+
+    fn key_with_category(&self, cards: &[CardType], category: usize) -> u64 {
+        let mut buffer = [0u8; Self::MAX_HAND_SIZE];
+        let strengths = &mut buffer[..cards.len()];
+        for (strength, &card) in strengths.iter_mut().zip(cards) {
+            *strength = self.strengths[card as usize];
+        }
+        match self.tie_break {
+            TieBreak::LeftToRight => {}
+            TieBreak::RightToLeft => strengths.reverse(),
+            TieBreak::HighestFirst => strengths.sort_unstable_by(|a, b| b.cmp(a)),
+        }
+
+        strengths
+            .iter()
+            .fold(category as u64, |key, &strength| key << 4 | strength as u64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Category {
+    pub name: String,
+    pub pattern: Pattern,
+}
+
+impl Category {
+    pub fn new(name: &str, pattern: Pattern) -> Self {
+        Self {
+            name: name.to_string(),
+            pattern,
+        }
+    }
+}
+
+impl From<HandType> for Category {
+    fn from(hand_type: HandType) -> Self {
+        let groups = match hand_type {
+            HandType::HighCard => vec![],
+            HandType::OnePair => vec![2],
+            HandType::TwoPair => vec![2, 2],
+            HandType::ThreeOfAKind => vec![3],
+            HandType::FullHouse => vec![3, 2],
+            HandType::FourOfAKind => vec![4],
+            HandType::FiveOfAKind => vec![5],
+        };
+        Self::new(&format!("{hand_type:?}"), Pattern::Counts(groups))
+    }
+}
+
+// `Counts` needs a group of at least that many matching cards for each entry, largest group
+// first, so `[3, 2]` is a full house. `Straight` needs that many cards of consecutive strength,
+// skipping wild cards in the ranking. Wild cards fill in for whatever is missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    Counts(Vec<usize>),
+    Straight(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    LeftToRight,
+    RightToLeft,
+    HighestFirst,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum CardType {
+    A,
+    K,
+    Q,
+    J,
+    T,
+    Nine,
+    Eight,
+    Seven,
+    Six,
+    Five,
+    Four,
+    Three,
+    Two,
+}
+
+impl TryFrom<char> for CardType {
+    type Error = ();
+
+    fn try_from(card: char) -> Result<Self, Self::Error> {
+        Ok(match card {
+            'A' => CardType::A,
+            'K' => CardType::K,
+            'Q' => CardType::Q,
+            'J' => CardType::J,
+            'T' => CardType::T,
+            '9' => CardType::Nine,
+            '8' => CardType::Eight,
+            '7' => CardType::Seven,
+            '6' => CardType::Six,
+            '5' => CardType::Five,
+            '4' => CardType::Four,
+            '3' => CardType::Three,
+            '2' => CardType::Two,
+            _ => return Err(()),
+        })
+    }
+}
+
+impl From<CardType> for char {
+    fn from(card: CardType) -> Self {
+        match card {
+            CardType::A => 'A',
+            CardType::K => 'K',
+            CardType::Q => 'Q',
+            CardType::J => 'J',
+            CardType::T => 'T',
+            CardType::Nine => '9',
+            CardType::Eight => '8',
+            CardType::Seven => '7',
+            CardType::Six => '6',
+            CardType::Five => '5',
+            CardType::Four => '4',
+            CardType::Three => '3',
+            CardType::Two => '2',
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Hand {
+    pub bid: i32,
+    pub category: usize,
+    pub cards: Vec<CardType>,
+    pub key: u64,
+}
+
+impl Hand {
+    pub fn new(cards: Vec<CardType>, bid: i32, ruleset: &Ruleset) -> Self {
+        let category = ruleset.category(&cards);
+        Self {
+            bid,
+            category,
+            key: ruleset.key_with_category(&cards, category),
+            cards,
+        }
+    }
+}
+
+// The categories of the default five-card rules, weakest first.
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    pub const ALL: [HandType; 7] = [
+        HandType::HighCard,
+        HandType::OnePair,
+        HandType::TwoPair,
+        HandType::ThreeOfAKind,
+        HandType::FullHouse,
+        HandType::FourOfAKind,
+        HandType::FiveOfAKind,
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CardType::*;
+    use HandType::*;
+
+    #[rstest::rstest]
+    #[case([A, A, A, A, A], FiveOfAKind)]
+    #[case([A, A, K, A, A], FourOfAKind)]
+    #[case([Two, Three, Two, Three, Three], FullHouse)]
+    #[case([T, T, T, Nine, Eight], ThreeOfAKind)]
+    #[case([Two, Three, Four, Three, Two], TwoPair)]
+    #[case([A, Two, Three, A, Four], OnePair)]
+    #[case([Two, Three, Four, Five, Six], HighCard)]
+    #[case([K, J, K, J, J], FullHouse)]
+    fn standard_hand_types(#[case] cards: [CardType; 5], #[case] expected: HandType) {
+        assert_eq!(Ruleset::standard().category(&cards), expected as usize);
+    }
+
+    #[test]
+    fn keys_order_hands() {
+        let ruleset = Ruleset::standard();
+        let key = |cards: [CardType; 5]| ruleset.key(&cards);
+        assert!(key([A, A, A, A, A]) > key([Two, Two, Two, Two, Three]));
+        assert!(key([Two, Two, Two, Two, Three]) > key([A, A, A, K, K]));
+        assert!(key([K, K, Six, Seven, Seven]) > key([K, T, J, J, T]));
+        assert!(key([Two, Three, Four, Five, Seven]) > key([Two, Three, Four, Five, Six]));
+        assert_eq!(key([K, T, J, J, T]), key([K, T, J, J, T]));
+    }
+
+    #[test]
+    fn jokers_rank_lowest() {
+        let ruleset = Ruleset::jokers();
+        assert!(ruleset.strength(J) < ruleset.strength(Two));
+        let standard = Ruleset::standard();
+        assert!(standard.strength(J) > standard.strength(T));
+    }
+
+    #[test]
+    fn tie_break_orders() {
+        let input = "2AAAA 1\n33332 2";
+        let ruleset = Ruleset::standard();
+        assert_eq!(
+            total_winnings(input, &ruleset, TiePolicy::InputOrder),
+            1 + 2 * 2
+        );
+
+        let ruleset = Ruleset::new(ruleset.ranking.clone(), vec![], TieBreak::RightToLeft);
+        assert_eq!(
+            total_winnings(input, &ruleset, TiePolicy::InputOrder),
+            2 + 2
+        );
+
+        let ruleset = Ruleset::new(ruleset.ranking.clone(), vec![], TieBreak::HighestFirst);
+        assert_eq!(
+            total_winnings(input, &ruleset, TiePolicy::InputOrder),
+            2 + 2
+        );
+    }
+
+    #[test]
+    fn winnings_beyond_i32() {
+        let input: String = (0..100_000)
+            .map(|i| format!("{} 1000\n", if i % 2 == 0 { "AAAAA" } else { "23456" }))
+            .collect();
+        let total = total_winnings(&input, &Ruleset::standard(), TiePolicy::InputOrder);
+        assert_eq!(total, 1000 * (100_000 * 100_001 / 2));
+        assert!(total > i32::MAX as i64);
+    }
+
+    #[test]
+    fn identical_hands_keep_every_bid() {
+        let input = "KK677 28\n32T3K 765\nKK677 10\nQQQJA 483";
+        let ruleset = Ruleset::standard();
+
+        let ranked = rank_hands(parse_hands(input, &ruleset), TiePolicy::InputOrder);
+        let ranks: Vec<(usize, i32)> = ranked.iter().map(|r| (r.rank, r.hand.bid)).collect();
+        assert_eq!(ranks, vec![(1, 765), (2, 28), (3, 10), (4, 483)]);
+        assert_eq!(
+            total_winnings(input, &ruleset, TiePolicy::InputOrder),
+            765 + 28 * 2 + 10 * 3 + 483 * 4
+        );
+
+        let ranked = rank_hands(parse_hands(input, &ruleset), TiePolicy::SharedRank);
+        let ranks: Vec<(usize, i32)> = ranked.iter().map(|r| (r.rank, r.hand.bid)).collect();
+        assert_eq!(ranks, vec![(1, 765), (2, 28), (2, 10), (4, 483)]);
+        assert_eq!(
+            total_winnings(input, &ruleset, TiePolicy::SharedRank),
+            765 + 28 * 2 + 10 * 2 + 483 * 4
+        );
+    }
+
+    #[test]
+    fn identical_joker_hands() {
+        let input = "JKKK2 1\nJKKK2 2\nJKKK2 4";
+        let ruleset = Ruleset::jokers();
+        assert_eq!(
+            total_winnings(input, &ruleset, TiePolicy::InputOrder),
+            1 + 4 + 12
+        );
+        assert_eq!(
+            total_winnings(input, &ruleset, TiePolicy::SharedRank),
+            1 + 2 + 4
+        );
+    }
+
+    #[test]
+    fn wild_cards_are_configurable() {
+        let ruleset = Ruleset::new(
+            Ruleset::standard().ranking,
+            vec![Two],
+            TieBreak::LeftToRight,
+        );
+        assert_eq!(
+            ruleset.category(&[Two, Two, A, K, Q]),
+            ThreeOfAKind as usize
+        );
+        assert_eq!(ruleset.category(&[J, J, A, K, Q]), OnePair as usize);
+    }
+
+    fn league_categories() -> Vec<Category> {
+        vec![
+            Category::new("high card", Pattern::Counts(vec![])),
+            Category::new("one pair", Pattern::Counts(vec![2])),
+            Category::new("two pair", Pattern::Counts(vec![2, 2])),
+            Category::new("three of a kind", Pattern::Counts(vec![3])),
+            Category::new("straight", Pattern::Straight(5)),
+            Category::new("full house", Pattern::Counts(vec![3, 2])),
+            Category::new("two triples", Pattern::Counts(vec![3, 3])),
+            Category::new("four of a kind", Pattern::Counts(vec![4])),
+        ]
+    }
+
+    fn category_name(ruleset: &Ruleset, cards: &[CardType]) -> String {
+        ruleset.categories()[ruleset.category(cards)].name.clone()
+    }
+
+    #[rstest::rstest]
+    #[case(vec![Two, Three, Four, Five, Six, K], "straight")]
+    #[case(vec![Nine, T, J, Q, K, A], "straight")]
+    #[case(vec![Two, Three, Four, Five, Seven, K], "high card")]
+    #[case(vec![Five, Five, Five, Nine, Nine, Nine], "two triples")]
+    #[case(vec![Five, Five, Five, Nine, Nine, Two], "full house")]
+    #[case(vec![Five, Five, Five, Five, Nine, Nine], "four of a kind")]
+    #[case(vec![Five, Five, Six, Six, Seven, Eight], "two pair")]
+    #[case(vec![Five, Five, Six, Seven, Eight, Nine], "straight")]
+    fn six_card_categories(#[case] cards: Vec<CardType>, #[case] expected: &str) {
+        let ruleset = Ruleset::standard()
+            .with_hand_size(6)
+            .with_categories(league_categories());
+        assert_eq!(category_name(&ruleset, &cards), expected);
+    }
+
+    #[rstest::rstest]
+    #[case(vec![J, Three, Four, Six, Seven, K], "straight")]
+    #[case(vec![Five, Five, J, Nine, Nine, Nine], "four of a kind")]
+    #[case(vec![J, Five, Five, Nine, Nine, K], "full house")]
+    #[case(vec![J, Five, Five, Nine, K, Two], "three of a kind")]
+    fn six_card_wild_categories(#[case] cards: Vec<CardType>, #[case] expected: &str) {
+        let ruleset = Ruleset::jokers()
+            .with_hand_size(6)
+            .with_categories(league_categories());
+        assert_eq!(category_name(&ruleset, &cards), expected);
+    }
+
+    #[test]
+    fn counts_in_any_order() {
+        let ruleset = Ruleset::standard().with_categories(vec![
+            Category::new("high card", Pattern::Counts(vec![])),
+            Category::new("full house", Pattern::Counts(vec![2, 3])),
+        ]);
+        assert_eq!(ruleset.categories()[1].pattern, Pattern::Counts(vec![3, 2]));
+        assert_eq!(category_name(&ruleset, &[K, K, K, Two, Two]), "full house");
+        assert_eq!(category_name(&ruleset, &[K, K, Q, Two, Two]), "high card");
+    }
+
+    #[test]
+    #[should_panic(expected = "straight \"empty\" should need at least one card")]
+    fn rejects_empty_straights() {
+        Ruleset::standard().with_categories(vec![
+            Category::new("high card", Pattern::Counts(vec![])),
+            Category::new("empty", Pattern::Straight(0)),
+        ]);
+    }
+
+    #[test]
+    fn variable_hand_sizes() {
+        let ruleset = Ruleset::standard().with_hand_size(4);
+        let input = "AAKK 1\n2222 2\nAKQT 3";
+        assert_eq!(
+            total_winnings(input, &ruleset, TiePolicy::InputOrder),
+            3 + 2 + 2 * 3
+        );
+
+        let ruleset = Ruleset::standard()
+            .with_hand_size(7)
+            .with_categories(league_categories());
+        let input = "2345678 1\nAAAKKKQ 2\n22223AK 3";
+        assert_eq!(
+            total_winnings(input, &ruleset, TiePolicy::InputOrder),
+            1 + 2 * 2 + 3 * 3
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Hand to have 5 cards")]
+    fn rejects_wrong_hand_size() {
+        parse_hands("AAKK 1", &Ruleset::standard());
+    }
+}
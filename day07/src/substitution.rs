@@ -0,0 +1,275 @@
+use crate::rules::{parse_hands, rank_hands, CardType, Ruleset, TiePolicy};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Substitution {
+    pub cards: Vec<CardType>,
+    pub replacements: Vec<Replacement>,
+    pub category: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Replacement {
+    pub position: usize,
+    pub wild: CardType,
+    pub becomes: CardType,
+}
+
+impl Ruleset {
+    // Tries every multiset of natural cards for the wild ones, strongest first, and keeps the
+    // first that reaches the hand's category. The substituted cards only decide the category;
+    // tie-breaks still use the wild cards.
+    pub fn best_substitution(&self, cards: &[CardType]) -> Substitution {
+        let category = self.category(cards);
+        let wild_positions: Vec<usize> = (0..cards.len())
+            .filter(|&i| self.is_wild(cards[i]))
+            .collect();
+        let naturals: Vec<CardType> = self
+            .ranking()
+            .iter()
+            .rev()
+            .filter(|&&card| !self.is_wild(card))
+            .copied()
+            .collect();
+
+        let substitute = |choice: &[usize]| {
+            let mut substituted = cards.to_vec();
+            for (&position, &natural) in wild_positions.iter().zip(choice) {
+                substituted[position] = naturals[natural];
+            }
+            substituted
+        };
+
+        let mut best = cards.to_vec();
+        if !wild_positions.is_empty() && !naturals.is_empty() {
+            let mut choice = vec![0; wild_positions.len()];
+            let mut best_category = None;
+            loop {
+                let substituted = substitute(&choice);
+                let reached = self.natural_category(&substituted);
+                if best_category.is_none_or(|best_category| reached > best_category) {
+                    best_category = Some(reached);
+                    best = substituted;
+                }
+                if reached == category {
+                    break;
+                }
+
+                let Some(i) = choice
+                    .iter()
+                    .rposition(|&natural| natural + 1 < naturals.len())
+                else {
+                    break;
+                };
+                let next = choice[i] + 1;
+                choice[i..].fill(next);
+            }
+        }
+
+        let replacements = wild_positions
+            .iter()
+            .map(|&position| Replacement {
+                position,
+                wild: cards[position],
+                becomes: best[position],
+            })
+            .collect();
+
+        Substitution {
+            cards: best,
+            replacements,
+            category,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub rank: usize,
+    pub cards: Vec<CardType>,
+    pub bid: i32,
+    pub substitution: Substitution,
+    pub winnings: i64,
+}
+
+pub fn explain(input: &str, ruleset: &Ruleset, tie_policy: TiePolicy) -> Vec<Explanation> {
+    rank_hands(parse_hands(input, ruleset), tie_policy)
+        .into_iter()
+        .map(|ranked| Explanation {
+            rank: ranked.rank,
+            cards: ranked.hand.cards.clone(),
+            bid: ranked.hand.bid,
+            substitution: ruleset.best_substitution(&ranked.hand.cards),
+            winnings: ranked.rank as i64 * ranked.hand.bid as i64,
+        })
+        .collect()
+}
+
+pub fn format_explanations(explanations: &[Explanation], ruleset: &Ruleset) -> String {
+    let hand = ruleset.hand_size().max("hand".len());
+    let category = ruleset
+        .categories()
+        .iter()
+        .map(|category| category.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("type".len());
+
+    let mut output = String::new();
+    writeln!(
+        output,
+        "{:>6}  {:<hand$}  {:<hand$}  {:<category$} {:>6} {:>12}",
+        "rank", "hand", "as", "type", "bid", "winnings"
+    )
+    .unwrap();
+
+    for explanation in explanations {
+        writeln!(
+            output,
+            "{:>6}  {:<hand$}  {:<hand$}  {:<category$} {:>6} {:>12}",
+            explanation.rank,
+            format_cards(&explanation.cards),
+            format_cards(&explanation.substitution.cards),
+            ruleset.categories()[explanation.substitution.category].name,
+            explanation.bid,
+            explanation.winnings
+        )
+        .unwrap();
+    }
+
+    let total: i64 = explanations
+        .iter()
+        .map(|explanation| explanation.winnings)
+        .sum();
+    writeln!(
+        output,
+        "{:>6}  {:<hand$}  {:<hand$}  {:<category$} {:>6} {:>12}",
+        "total", "", "", "", "", total
+    )
+    .unwrap();
+    output
+}
+
+fn format_cards(cards: &[CardType]) -> String {
+    cards.iter().map(|&card| char::from(card)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{Category, HandType, Pattern};
+    use rstest::rstest;
+    use CardType::*;
+
+    #[rstest]
+    #[case([T, Five, Five, J, Five], [T, Five, Five, Five, Five], HandType::FourOfAKind)]
+    #[case([K, T, J, J, T], [K, T, T, T, T], HandType::FourOfAKind)]
+    #[case([Q, Q, Q, J, A], [Q, Q, Q, Q, A], HandType::FourOfAKind)]
+    #[case([K, K, Q, Q, J], [K, K, Q, Q, K], HandType::FullHouse)]
+    #[case([Two, Three, Four, Five, J], [Two, Three, Four, Five, Five], HandType::OnePair)]
+    #[case([J, J, J, J, J], [A, A, A, A, A], HandType::FiveOfAKind)]
+    #[case([Three, Two, T, Three, K], [Three, Two, T, Three, K], HandType::OnePair)]
+    fn substitutes_jokers(
+        #[case] cards: [CardType; 5],
+        #[case] expected: [CardType; 5],
+        #[case] hand_type: HandType,
+    ) {
+        let substitution = Ruleset::jokers().best_substitution(&cards);
+        assert_eq!(substitution.cards, expected);
+        assert_eq!(substitution.category, hand_type as usize);
+        assert_eq!(
+            Ruleset::standard().category(&substitution.cards),
+            hand_type as usize
+        );
+    }
+
+    #[test]
+    fn aligns_tables_for_any_hand_size() {
+        let ruleset = Ruleset::jokers().with_hand_size(7).with_categories(vec![
+            Category::new("nothing", Pattern::Counts(vec![])),
+            Category::new("three of a kind", Pattern::Counts(vec![3])),
+        ]);
+        let input = "23456J8 10\nAAJ2345 20";
+        let table = format_explanations(&explain(input, &ruleset, TiePolicy::InputOrder), &ruleset);
+
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].contains("23456J8  23456A8  nothing"), "{table}");
+        assert!(
+            lines.iter().all(|line| line.len() == lines[0].len()),
+            "{table}"
+        );
+    }
+
+    #[test]
+    fn substitutes_for_custom_categories() {
+        let ruleset = Ruleset::jokers().with_hand_size(6).with_categories(vec![
+            Category::new("high card", Pattern::Counts(vec![])),
+            Category::new("one pair", Pattern::Counts(vec![2])),
+            Category::new("straight", Pattern::Straight(6)),
+            Category::new("two triples", Pattern::Counts(vec![3, 3])),
+        ]);
+
+        let substitution = ruleset.best_substitution(&[Nine, J, J, Q, K, A]);
+        assert_eq!(substitution.cards, vec![Nine, T, Eight, Q, K, A]);
+        assert_eq!(ruleset.categories()[substitution.category].name, "straight");
+
+        let substitution = ruleset.best_substitution(&[Four, Four, J, J, Six, Six]);
+        assert_eq!(substitution.cards, vec![Four, Four, Six, Four, Six, Six]);
+        assert_eq!(
+            ruleset.categories()[substitution.category].name,
+            "two triples"
+        );
+    }
+
+    #[test]
+    fn records_each_replacement() {
+        let substitution = Ruleset::jokers().best_substitution(&[K, T, J, J, T]);
+        assert_eq!(
+            substitution.replacements,
+            vec![
+                Replacement {
+                    position: 2,
+                    wild: J,
+                    becomes: T
+                },
+                Replacement {
+                    position: 3,
+                    wild: J,
+                    becomes: T
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn explains_example() {
+        let input = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+        let explanations = explain(input, &Ruleset::jokers(), TiePolicy::InputOrder);
+        let ranks: Vec<(String, usize, i64)> = explanations
+            .iter()
+            .map(|explanation| {
+                (
+                    format_cards(&explanation.cards),
+                    explanation.rank,
+                    explanation.winnings,
+                )
+            })
+            .collect();
+        assert_eq!(
+            ranks,
+            vec![
+                ("32T3K".to_string(), 1, 765),
+                ("KK677".to_string(), 2, 56),
+                ("T55J5".to_string(), 3, 2052),
+                ("QQQJA".to_string(), 4, 1932),
+                ("KTJJT".to_string(), 5, 1100),
+            ]
+        );
+        assert!(format_explanations(&explanations, &Ruleset::jokers()).ends_with("5905\n"));
+    }
+}
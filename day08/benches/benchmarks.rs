@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 fn main() {
     divan::main();
 }
@@ -13,3 +15,59 @@ fn part2() {
     let input = include_str!("../input.txt");
     day08::part2(divan::black_box(input));
 }
+
+// Baselines that walk the same steps as part1 and part2 over string-keyed nodes, as the
+// network was stored before node names were interned.
+#[divan::bench]
+fn part1_string_keys() -> usize {
+    let (instructions, nodes) = string_keyed(divan::black_box(include_str!("../input.txt")));
+    let mut key = "AAA";
+    let mut steps = 0;
+    for instruction in instructions.chars().cycle() {
+        key = step(&nodes, key, instruction);
+        steps += 1;
+        if key == "ZZZ" {
+            break;
+        }
+    }
+    steps
+}
+
+#[divan::bench]
+fn part2_string_keys() -> usize {
+    let (instructions, nodes) = string_keyed(divan::black_box(include_str!("../input.txt")));
+    let instructions: Vec<char> = instructions.chars().collect();
+    nodes
+        .keys()
+        .filter(|key| key.ends_with('A'))
+        .map(|&start| {
+            let mut seen: HashMap<(&str, usize), usize> = HashMap::new();
+            let mut key = start;
+            for steps in 0.. {
+                let index = steps % instructions.len();
+                if seen.insert((key, index), steps).is_some() {
+                    return steps;
+                }
+                key = step(&nodes, key, instructions[index]);
+            }
+            unreachable!()
+        })
+        .sum()
+}
+
+fn string_keyed(input: &str) -> (&str, HashMap<&str, (&str, &str)>) {
+    let (instructions, nodes) = input.split_once("\n\n").unwrap();
+    let nodes = nodes
+        .lines()
+        .map(|line| (&line[0..3], (&line[7..10], &line[12..15])))
+        .collect();
+    (instructions, nodes)
+}
+
+fn step<'a>(nodes: &HashMap<&'a str, (&'a str, &'a str)>, key: &str, instruction: char) -> &'a str {
+    let (left, right) = nodes[key];
+    match instruction {
+        'L' => left,
+        _ => right,
+    }
+}
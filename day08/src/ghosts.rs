@@ -0,0 +1,267 @@
+use crate::network::Network;
+
+// A ghost's walk is fully determined by its (node, instruction index) state, so it always ends
+// up looping. Steps before `prefix` happen once; from then on the walk repeats every `length`
+// steps. `hits` lists every step in the first `prefix + length` that lands on a goal node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle<'a> {
+    pub start: &'a str,
+    pub prefix: usize,
+    pub length: usize,
+    pub hits: Vec<usize>,
+}
+
+impl GhostCycle<'_> {
+    pub fn is_hit(&self, step: usize) -> bool {
+        let step = if step < self.prefix {
+            step
+        } else {
+            self.prefix + (step - self.prefix) % self.length
+        };
+        self.hits.binary_search(&step).is_ok()
+    }
+
+    // Residues modulo `length` of the hits that keep recurring.
+    fn cycle_residues(&self) -> impl Iterator<Item = usize> + '_ {
+        self.hits
+            .iter()
+            .filter(|&&hit| hit >= self.prefix)
+            .map(|&hit| hit % self.length)
+    }
+}
+
+impl<'a> Network<'a> {
+    pub fn ghost_cycle(&self, start: usize, is_goal: impl Fn(&str) -> bool) -> GhostCycle<'a> {
+        let goals: Vec<bool> = self.names().iter().map(|name| is_goal(name)).collect();
+        let instruction_count = self.instructions.len();
+        // The first step at which each (node, instruction index) state was reached.
+        let mut seen = vec![usize::MAX; self.len() * instruction_count];
+        let mut hits = vec![];
+        let mut node = start;
+
+        for step in 0.. {
+            let instruction_index = step % instruction_count;
+            let state = node * instruction_count + instruction_index;
+            if seen[state] != usize::MAX {
+                return GhostCycle {
+                    start: self.name(start),
+                    prefix: seen[state],
+                    length: step - seen[state],
+                    hits,
+                };
+            }
+            seen[state] = step;
+
+            if goals[node] {
+                hits.push(step);
+            }
+            node = self.next(node, self.instructions[instruction_index]);
+        }
+        unreachable!("a finite network should always loop")
+    }
+}
+
+// The first step at which every ghost stands on a goal node. Steps before the last ghost
+// enters its loop are checked directly; later steps are found by combining each ghost's
+// recurring hits with the Chinese Remainder Theorem.
+pub fn simultaneous_arrival(cycles: &[GhostCycle]) -> Result<usize, String> {
+    let latest = cycles
+        .iter()
+        .max_by_key(|cycle| cycle.prefix)
+        .ok_or("there are no ghosts to walk")?;
+    if let Some(cycle) = cycles.iter().find(|cycle| cycle.hits.is_empty()) {
+        return Err(format!(
+            "the ghost starting at {} never reaches a goal node",
+            cycle.start
+        ));
+    }
+
+    if let Some(step) = latest
+        .hits
+        .iter()
+        .take_while(|&&hit| hit < latest.prefix)
+        .find(|&&hit| cycles.iter().all(|cycle| cycle.is_hit(hit)))
+    {
+        return Ok(*step);
+    }
+
+    let mut modulus = 1u128;
+    let mut residues = vec![0u128];
+    for cycle in cycles {
+        let length = cycle.length as u128;
+        let mut combined = vec![];
+        for &residue in residues.iter() {
+            for other in cycle.cycle_residues() {
+                if let Some(solution) = crt((residue, modulus), (other as u128, length)) {
+                    combined.push(solution);
+                }
+            }
+        }
+        modulus = lcm(modulus, length)
+            .filter(|&modulus| modulus <= usize::MAX as u128)
+            .ok_or("the ghosts' combined cycle length overflows")?;
+        combined.sort_unstable();
+        combined.dedup();
+        residues = combined;
+    }
+
+    let earliest = latest.prefix as u128;
+    residues
+        .into_iter()
+        .map(|residue| {
+            if residue >= earliest {
+                residue
+            } else {
+                residue + (earliest - residue).div_ceil(modulus) * modulus
+            }
+        })
+        .min()
+        .and_then(|step| usize::try_from(step).ok())
+        .ok_or_else(|| "the ghosts never stand on goal nodes at the same step".to_string())
+}
+
+// Solves x = a (mod m) and x = b (mod n) for moduli that need not be coprime, returning the
+// solution modulo lcm(m, n).
+fn crt((a, m): (u128, u128), (b, n): (u128, u128)) -> Option<u128> {
+    let (g, p, _) = extended_gcd(m as i128, n as i128);
+    let difference = b as i128 - a as i128;
+    if difference % g != 0 {
+        return None;
+    }
+
+    let reduced = n as i128 / g;
+    let step = (difference / g).rem_euclid(reduced) * p.rem_euclid(reduced) % reduced;
+    Some((a + step as u128 * m) % (m * reduced as u128))
+}
+
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+fn lcm(a: u128, b: u128) -> Option<u128> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycles(input: &str) -> Vec<GhostCycle<'_>> {
+        let network = Network::try_from(input).unwrap();
+        let mut starts: Vec<&str> = network
+            .names()
+            .iter()
+            .copied()
+            .filter(|name| name.ends_with('A'))
+            .collect();
+        starts.sort_unstable();
+        starts
+            .into_iter()
+            .map(|start| {
+                let id = network.id(start).unwrap();
+                network.ghost_cycle(id, |name| name.ends_with('Z'))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn example_cycles() {
+        let cycles = cycles(include_str!("../example3.txt"));
+        assert_eq!(
+            cycles,
+            vec![
+                GhostCycle {
+                    start: "11A",
+                    prefix: 1,
+                    length: 2,
+                    hits: vec![2],
+                },
+                GhostCycle {
+                    start: "22A",
+                    prefix: 1,
+                    length: 6,
+                    hits: vec![3, 6],
+                },
+            ]
+        );
+        assert_eq!(simultaneous_arrival(&cycles), Ok(6));
+    }
+
+    #[test]
+    fn offset_cycles() {
+        // 11A first reaches 11Z after 1 step and then every 3 steps; 22A after 2 and then every
+        // 2, so the answer is 4 rather than lcm(1, 2).
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11C, 11C)
+11C = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)";
+        assert_eq!(simultaneous_arrival(&cycles(input)), Ok(4));
+    }
+
+    #[test]
+    fn arrival_before_every_loop() {
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22Z, 22Z)";
+        assert_eq!(simultaneous_arrival(&cycles(input)), Ok(1));
+    }
+
+    #[test]
+    fn no_simultaneous_arrival() {
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)";
+        assert_eq!(
+            simultaneous_arrival(&cycles(input)),
+            Err("the ghosts never stand on goal nodes at the same step".to_string())
+        );
+    }
+
+    #[test]
+    fn ghost_never_arrives() {
+        let input = "L
+
+11A = (11B, 11B)
+11B = (11A, 11A)
+22A = (22Z, 22Z)
+22Z = (22Z, 22Z)";
+        assert_eq!(
+            simultaneous_arrival(&cycles(input)),
+            Err("the ghost starting at 11A never reaches a goal node".to_string())
+        );
+    }
+
+    #[test]
+    fn crt_with_shared_factors() {
+        assert_eq!(crt((1, 3), (0, 2)), Some(4));
+        assert_eq!(crt((2, 4), (4, 6)), Some(10));
+        assert_eq!(crt((1, 4), (0, 6)), None);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod ghosts;
+pub mod network;
 mod part1;
 mod part2;
 
@@ -0,0 +1,152 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, alphanumeric1, line_ending, multispace1},
+    combinator::eof,
+    multi::many1,
+    sequence::{separated_pair, terminated},
+    IResult, Parser,
+};
+use std::collections::HashMap;
+
+// Node names are interned to dense ids when parsing, so walks only index into the flat
+// `left`/`right` successor arrays.
+#[derive(Debug)]
+pub struct Network<'a> {
+    pub instructions: Vec<Instruction>,
+    names: Vec<&'a str>,
+    ids: HashMap<&'a str, usize>,
+    left: Vec<usize>,
+    right: Vec<usize>,
+}
+
+impl<'a> Network<'a> {
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &'a str {
+        self.names[id]
+    }
+
+    pub fn names(&self) -> &[&'a str] {
+        &self.names
+    }
+
+    pub fn next(&self, id: usize, instruction: Instruction) -> usize {
+        match instruction {
+            Instruction::Left => self.left[id],
+            Instruction::Right => self.right[id],
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Network<'a> {
+    type Error = String;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        let (input, instructions) =
+            instructions_parser(input).map_err(|e| format!("invalid instructions: {e}"))?;
+        let (input, nodes) = nodes_parser(input).map_err(|e| format!("invalid nodes: {e}"))?;
+        if !input.is_empty() {
+            return Err(format!("unexpected trailing input: {input:?}"));
+        }
+
+        let names: Vec<&str> = nodes.iter().map(|&(key, _)| key).collect();
+        let ids: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(id, &key)| (key, id))
+            .collect();
+        if ids.len() != names.len() {
+            return Err("node names should be unique".to_string());
+        }
+
+        let lookup = |key: &str, successor: &str| {
+            ids.get(successor)
+                .copied()
+                .ok_or_else(|| format!("node {key} leads to unknown node {successor}"))
+        };
+        let mut left = Vec::with_capacity(nodes.len());
+        let mut right = Vec::with_capacity(nodes.len());
+        for (key, node) in nodes.iter() {
+            left.push(lookup(key, node.left)?);
+            right.push(lookup(key, node.right)?);
+        }
+
+        Ok(Self {
+            instructions,
+            names,
+            ids,
+            left,
+            right,
+        })
+    }
+}
+
+fn instructions_parser(input: &str) -> IResult<&str, Vec<Instruction>> {
+    let (input, instructions) = many1(alt((
+        complete::char('L').map(|_| Instruction::Left),
+        complete::char('R').map(|_| Instruction::Right),
+    )))(input)?;
+    let (input, _) = multispace1(input)?;
+    Ok((input, instructions))
+}
+
+fn nodes_parser(input: &str) -> IResult<&str, Vec<(&str, Node<'_>)>> {
+    many1(terminated(node_parser, alt((line_ending, eof))))(input)
+}
+
+fn node_parser(input: &str) -> IResult<&str, (&str, Node<'_>)> {
+    let (input, key) = alphanumeric1(input)?;
+    let (input, _) = tag(" = (")(input)?;
+    let (input, (left, right)) = separated_pair(alphanumeric1, tag(", "), alphanumeric1)(input)?;
+    let (input, _) = tag(")")(input)?;
+    let node = Node { left, right };
+    Ok((input, (key, node)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+struct Node<'a> {
+    left: &'a str,
+    right: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interns_nodes() {
+        let network = Network::try_from(include_str!("../example.txt")).unwrap();
+        let aaa = network.id("AAA").unwrap();
+        let ccc = network.id("CCC").unwrap();
+        assert_eq!(network.name(aaa), "AAA");
+        assert_eq!(network.name(network.next(aaa, Instruction::Right)), "CCC");
+        assert_eq!(network.name(network.next(ccc, Instruction::Right)), "GGG");
+        assert_eq!(network.id("QQQ"), None);
+    }
+
+    #[test]
+    fn rejects_unknown_successors() {
+        let input = "L\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+        assert_eq!(
+            Network::try_from(input).unwrap_err(),
+            "node AAA leads to unknown node BBB"
+        );
+    }
+}
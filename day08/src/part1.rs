@@ -1,41 +1,26 @@
+use crate::network::Network;
 use itertools::{FoldWhile, Itertools};
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, alpha1, line_ending, multispace1},
-    combinator::eof,
-    multi::{fold_many1, many1},
-    sequence::{separated_pair, terminated},
-    IResult, Parser,
-};
-use std::collections::HashMap;
 
 pub fn part1(input: &str) -> i32 {
-    let (input, instructions) =
-        instructions_parser(input).expect("instructions should be parsable");
-    let (input, nodes) = nodes_parser(input).expect("nodes should be parsable");
-    debug_assert_eq!(input, "", "input should be consumed");
+    let network = Network::try_from(input).expect("network should be parsable");
+    let start = network.id("AAA").expect("network should have node AAA");
+    let goal = network.id("ZZZ").expect("network should have node ZZZ");
 
-    instructions
+    network
+        .instructions
         .iter()
         .cycle()
         .enumerate()
         .fold_while(
-            (0, "AAA"),
-            |(_, current_node_key), (steps_count, instruction)| {
-                let node = nodes
-                    .get(current_node_key)
-                    .expect("node_key should always match a valid node");
-
-                let next_node_key = match instruction {
-                    Instruction::Left => node.left,
-                    Instruction::Right => node.right,
-                };
-
-                let results = (steps_count as i32 + 1, next_node_key);
-                match next_node_key {
-                    "ZZZ" => FoldWhile::Done(results),
-                    _ => FoldWhile::Continue(results),
+            (0, start),
+            |(_, current_node), (steps_count, &instruction)| {
+                let next_node = network.next(current_node, instruction);
+
+                let results = (steps_count as i32 + 1, next_node);
+                if next_node == goal {
+                    FoldWhile::Done(results)
+                } else {
+                    FoldWhile::Continue(results)
                 }
             },
         )
@@ -43,47 +28,6 @@ pub fn part1(input: &str) -> i32 {
         .0
 }
 
-fn instructions_parser(input: &str) -> IResult<&str, Vec<Instruction>> {
-    let (input, instructions) = many1(alt((
-        complete::char('L').map(|_| Instruction::Left),
-        complete::char('R').map(|_| Instruction::Right),
-    )))(input)?;
-    let (input, _) = multispace1(input)?;
-    Ok((input, instructions))
-}
-
-fn nodes_parser(input: &str) -> IResult<&str, HashMap<&str, Node>> {
-    fold_many1(
-        terminated(node_parser, alt((line_ending, eof))),
-        HashMap::new,
-        |mut nodes, (key, node)| {
-            nodes.insert(key, node);
-            nodes
-        },
-    )(input)
-}
-
-fn node_parser(input: &str) -> IResult<&str, (&str, Node)> {
-    let (input, key) = alpha1(input)?;
-    let (input, _) = tag(" = (")(input)?;
-    let (input, (left, right)) = separated_pair(alpha1, tag(", "), alpha1)(input)?;
-    let (input, _) = tag(")")(input)?;
-    let node = Node { left, right };
-    Ok((input, (key, node)))
-}
-
-#[derive(Debug)]
-enum Instruction {
-    Left,
-    Right,
-}
-
-#[derive(Debug)]
-struct Node<'a> {
-    left: &'a str,
-    right: &'a str,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;